@@ -0,0 +1,110 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use super::BacklinksArgs;
+use crate::config::KbConfig;
+use crate::fs::{display_relative, relative_link, resolve_claude_root_from_cwd, walk_kb_documents};
+use crate::graph::KbGraph;
+use crate::markdown::{escape_link_text, replace_marked_section, Placement, BACKLINKS_SECTION};
+use crate::model::Document;
+
+pub fn run(args: BacklinksArgs) -> Result<()> {
+    let (cwd, claude_root) = resolve_claude_root_from_cwd()?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            cwd.display()
+        );
+    }
+
+    let workspace = claude_root.parent().unwrap_or(&claude_root);
+
+    if args.sync {
+        let updated = sync_sections(&claude_root)?;
+        if updated.is_empty() {
+            println!("Backlink sections already up to date.");
+        }
+        for path in updated {
+            println!("Updated {}", display_relative(workspace, &path));
+        }
+        return Ok(());
+    }
+
+    let Some(link) = args.link.as_deref() else {
+        bail!("Provide a link or pass --sync.");
+    };
+
+    let graph = KbGraph::load(&claude_root)?;
+    if !graph.contains(link) {
+        bail!("No document found with link `{link}`.");
+    }
+
+    let sources: Vec<&str> = graph.incoming(link).collect();
+    if sources.is_empty() {
+        println!("No documents reference `{link}`.");
+        return Ok(());
+    }
+
+    for (index, source) in sources.iter().enumerate() {
+        let Some(node) = graph.node(source) else {
+            continue;
+        };
+        println!(
+            "{}. {} — {} (type: {})",
+            index + 1,
+            display_relative(workspace, &node.path),
+            node.title,
+            node.doc_type
+        );
+    }
+
+    Ok(())
+}
+
+pub(crate) fn sync_sections(claude_root: &Path) -> Result<Vec<PathBuf>> {
+    let config = KbConfig::load(claude_root)?;
+    let entries = walk_kb_documents(claude_root).collect::<Result<Vec<_>>>()?;
+    let graph = KbGraph::from_entries(&entries, config.slug_policy);
+    let mut updated = Vec::new();
+
+    for entry in &entries {
+        let front = &entry.document.front_matter;
+        let mut referrers: Vec<_> = graph
+            .incoming(&front.link)
+            .filter_map(|source| graph.node(source))
+            .collect();
+        referrers.sort_by(|a, b| a.title.cmp(&b.title).then_with(|| a.link.cmp(&b.link)));
+
+        let section = (!referrers.is_empty()).then(|| {
+            let mut lines = vec!["## Referenced by".to_string(), String::new()];
+            for node in &referrers {
+                lines.push(format!(
+                    "- [{}]({})",
+                    escape_link_text(&node.title),
+                    relative_link(&entry.path, &node.path)
+                ));
+            }
+            lines.join("\n")
+        });
+
+        let body = replace_marked_section(
+            &entry.document.body,
+            BACKLINKS_SECTION,
+            section.as_deref(),
+            Placement::End,
+        );
+        if body.trim_end() == entry.document.body.trim_end() {
+            continue;
+        }
+
+        let document = Document::new(entry.document.front_matter.clone(), body);
+        fs::write(&entry.path, document.to_markdown()?)
+            .with_context(|| format!("Unable to write {}", entry.path.display()))?;
+        updated.push(entry.path.clone());
+    }
+
+    Ok(updated)
+}
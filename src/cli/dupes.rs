@@ -0,0 +1,60 @@
+use anyhow::{bail, Result};
+
+use super::DupesArgs;
+use crate::fs::{display_relative, resolve_claude_root, walk_kb_documents};
+use crate::similarity::title_similarity;
+
+pub fn run(args: DupesArgs) -> Result<()> {
+    let (base_dir, claude_root) = resolve_claude_root(args.directory.as_deref())?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            base_dir.display()
+        );
+    }
+
+    let workspace = claude_root.parent().unwrap_or(&claude_root);
+    let entries = walk_kb_documents(&claude_root).collect::<Result<Vec<_>>>()?;
+
+    let mut pairs = Vec::new();
+    for (index, left) in entries.iter().enumerate() {
+        for right in &entries[index + 1..] {
+            let (left_front, right_front) =
+                (&left.document.front_matter, &right.document.front_matter);
+            let score = title_similarity(
+                &left_front.title,
+                &left_front.link,
+                &right_front.title,
+                &right_front.link,
+            );
+            if score >= args.threshold {
+                pairs.push((score, left, right));
+            }
+        }
+    }
+
+    if pairs.is_empty() {
+        println!("No likely duplicates found.");
+        return Ok(());
+    }
+
+    pairs.sort_by(|left, right| {
+        right
+            .0
+            .total_cmp(&left.0)
+            .then_with(|| left.1.path.cmp(&right.1.path))
+            .then_with(|| left.2.path.cmp(&right.2.path))
+    });
+
+    for (index, (score, left, right)) in pairs.iter().enumerate() {
+        println!(
+            "{}. {} ↔ {} (similarity: {score:.2})",
+            index + 1,
+            display_relative(workspace, &left.path),
+            display_relative(workspace, &right.path)
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use std::fs;
+use std::path::{Component, Path};
+
+use anyhow::{bail, Context, Result};
+use chrono_tz::Tz;
+
+use super::{ExportArgs, ExportCommand, SiteArgs};
+use crate::config::KbConfig;
+use crate::fs::{
+    display_relative, normalize_path, resolve_claude_root, walk_kb_documents, CURRENT_DIR_ERROR,
+};
+use crate::site::{asset_files, Site};
+
+pub fn run(args: ExportArgs) -> Result<()> {
+    match args.command {
+        ExportCommand::Site(args) => export_site(args),
+    }
+}
+
+fn export_site(args: SiteArgs) -> Result<()> {
+    let (base_dir, claude_root) = resolve_claude_root(args.directory.as_deref())?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            base_dir.display()
+        );
+    }
+
+    let current_dir = std::env::current_dir().context(CURRENT_DIR_ERROR)?;
+    let output = normalize_path(&current_dir.join(&args.output));
+    if output.starts_with(normalize_path(&claude_root)) {
+        bail!(
+            "Refusing to export into {}; choose a directory outside .claude.",
+            output.display()
+        );
+    }
+
+    let config = KbConfig::load(&claude_root)?;
+    let timezone = config.display_timezone.unwrap_or(Tz::UTC);
+    let entries = walk_kb_documents(&claude_root).collect::<Result<Vec<_>>>()?;
+    let site = Site::build(&claude_root, &entries, timezone, config.slug_policy);
+
+    for (relative, content) in site.pages() {
+        write_file(&output, relative, content.as_bytes())?;
+    }
+
+    let mut assets = asset_files(&claude_root);
+    assets.retain(|(relative, source)| {
+        let clashes = site.page(relative).is_some();
+        if clashes {
+            println!(
+                "Warning: skipping {}; the site already has a page at {relative}.",
+                display_relative(&base_dir, source)
+            );
+        }
+        !clashes
+    });
+    for (relative, source) in &assets {
+        let bytes =
+            fs::read(source).with_context(|| format!("Unable to read {}", source.display()))?;
+        write_file(&output, relative, &bytes)?;
+    }
+
+    println!(
+        "Exported {} document(s) as {} page(s) and {} asset(s) to {}",
+        entries.len(),
+        site.len(),
+        assets.len(),
+        output.display()
+    );
+
+    Ok(())
+}
+
+fn write_file(output: &Path, relative: &str, contents: &[u8]) -> Result<()> {
+    if !Path::new(relative)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        bail!("Refusing to write {relative} outside {}", output.display());
+    }
+    let path = output.join(relative);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Unable to create {}", parent.display()))?;
+    }
+    fs::write(&path, contents).with_context(|| format!("Unable to write {}", path.display()))
+}
@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use super::toc::with_toc;
+use super::FmtArgs;
+use crate::fs::{display_relative, resolve_claude_root, walk_kb_files};
+use crate::model::Document;
+
+pub fn run(args: FmtArgs) -> Result<()> {
+    let (target_dir, claude_root) = resolve_claude_root(args.directory.as_deref())?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            target_dir.display()
+        );
+    }
+
+    let workspace = claude_root
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| target_dir.to_path_buf());
+
+    let mut changed = 0;
+    let mut failures = 0;
+
+    for path_result in walk_kb_files(&claude_root) {
+        let path = path_result?;
+        let display = display_relative(&workspace, &path);
+
+        let raw = fs::read_to_string(&path)
+            .with_context(|| format!("Unable to read {}", path.display()))?;
+        let parsed = Document::parse(&raw).map(|mut document| {
+            document.front_matter.canonicalize();
+            if args.toc {
+                document.body = with_toc(&document.body);
+            }
+            document
+        });
+        let formatted = match parsed.and_then(|document| document.to_markdown()) {
+            Ok(formatted) => formatted,
+            Err(error) => {
+                println!("error: {display} — {error:#}");
+                failures += 1;
+                continue;
+            }
+        };
+
+        if formatted == raw {
+            continue;
+        }
+
+        changed += 1;
+        if args.check {
+            println!("Would reformat {display}");
+        } else {
+            fs::write(&path, formatted)
+                .with_context(|| format!("Unable to write {}", path.display()))?;
+            println!("Formatted {display}");
+        }
+    }
+
+    if failures > 0 {
+        bail!("Unable to format {failures} document(s).");
+    }
+
+    if args.check && changed > 0 {
+        bail!("{changed} document(s) need formatting; run `kb-claude fmt`.");
+    }
+
+    if changed == 0 {
+        println!("All documents already formatted.");
+    } else {
+        println!("Formatted {changed} document(s).");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+use super::{GraphArgs, GraphFormat};
+use crate::fs::{display_relative, resolve_claude_root};
+use crate::graph::{EdgeKind, GraphEdge, KbGraph};
+
+pub fn run(args: GraphArgs) -> Result<()> {
+    let (base_dir, claude_root) = resolve_claude_root(args.directory.as_deref())?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            base_dir.display()
+        );
+    }
+
+    let workspace = claude_root.parent().unwrap_or(&claude_root).to_path_buf();
+    let mut graph = KbGraph::load(&claude_root)?;
+
+    if let Some(link) = args.around.as_deref() {
+        if !graph.contains(link) {
+            bail!("No document found with link `{link}`.");
+        }
+        let neighborhood = graph.neighborhood(link, args.depth);
+        graph.retain(|node| neighborhood.contains(&node.link));
+    }
+
+    if let Some(doc_type) = args.doc_type.as_deref() {
+        graph.retain(|node| node.doc_type == doc_type);
+    }
+
+    if let Some(tag) = args.tag.as_deref() {
+        let needle = tag.to_lowercase();
+        graph.retain(|node| node.tags.iter().any(|tag| tag.to_lowercase() == needle));
+    }
+
+    let rendered = render(&graph, &workspace, args.format)?;
+
+    match args.output {
+        Some(output) => {
+            let output_path = resolve_output_path(&base_dir, &output);
+            if let Some(parent) = output_path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Unable to prepare {}", parent.display()))?;
+            }
+            fs::write(&output_path, rendered)
+                .with_context(|| format!("Unable to write {}", output_path.display()))?;
+            println!(
+                "Wrote graph to {}",
+                display_relative(&base_dir, &output_path)
+            );
+        }
+        None => print!("{rendered}"),
+    }
+
+    Ok(())
+}
+
+pub(crate) fn render(graph: &KbGraph, workspace: &Path, format: GraphFormat) -> Result<String> {
+    match format {
+        GraphFormat::Dot => Ok(render_dot(graph)),
+        GraphFormat::Mermaid => Ok(render_mermaid(graph)),
+        GraphFormat::Json => render_json(graph, workspace),
+        GraphFormat::Graphml => Ok(render_graphml(graph)),
+    }
+}
+
+fn render_dot(graph: &KbGraph) -> String {
+    let mut lines = vec!["digraph kb {".to_string()];
+    for node in graph.nodes() {
+        lines.push(format!(
+            "  \"{}\" [label=\"{}\", type=\"{}\", tags=\"{}\"];",
+            escape_dot(&node.link),
+            escape_dot(&node.title),
+            escape_dot(&node.doc_type),
+            escape_dot(&node.tags.join(","))
+        ));
+    }
+    for edge in graph.edges() {
+        let style = match edge.kind {
+            EdgeKind::Relation => "",
+            EdgeKind::Wikilink => " [style=dashed]",
+        };
+        lines.push(format!(
+            "  \"{}\" -> \"{}\"{style};",
+            escape_dot(&edge.source),
+            escape_dot(&edge.target)
+        ));
+    }
+    lines.push("}".to_string());
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn render_mermaid(graph: &KbGraph) -> String {
+    let ids: HashMap<&str, usize> = graph
+        .nodes()
+        .enumerate()
+        .map(|(index, node)| (node.link.as_str(), index))
+        .collect();
+    let node_id = |link: &str| {
+        ids.get(link)
+            .map(|index| format!("n{index}"))
+            .unwrap_or_default()
+    };
+
+    let mut lines = vec!["graph LR".to_string()];
+    for node in graph.nodes() {
+        lines.push(format!(
+            "  {}[\"{}\"]",
+            node_id(&node.link),
+            escape_mermaid(&node.title)
+        ));
+    }
+    for edge in graph.edges() {
+        let arrow = match edge.kind {
+            EdgeKind::Relation => "-->",
+            EdgeKind::Wikilink => "-.->",
+        };
+        lines.push(format!(
+            "  {} {arrow} {}",
+            node_id(&edge.source),
+            node_id(&edge.target)
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[derive(Serialize)]
+struct JsonGraph<'a> {
+    nodes: Vec<JsonNode<'a>>,
+    edges: Vec<&'a GraphEdge>,
+}
+
+#[derive(Serialize)]
+struct JsonNode<'a> {
+    link: &'a str,
+    title: &'a str,
+    #[serde(rename = "type")]
+    doc_type: &'a str,
+    tags: &'a [String],
+    path: String,
+}
+
+fn render_json(graph: &KbGraph, workspace: &Path) -> Result<String> {
+    let payload = JsonGraph {
+        nodes: graph
+            .nodes()
+            .map(|node| JsonNode {
+                link: &node.link,
+                title: &node.title,
+                doc_type: &node.doc_type,
+                tags: &node.tags,
+                path: display_relative(workspace, &node.path),
+            })
+            .collect(),
+        edges: graph.edges().collect(),
+    };
+    let mut json =
+        serde_json::to_string_pretty(&payload).with_context(|| "Unable to serialize graph")?;
+    json.push('\n');
+    Ok(json)
+}
+
+fn render_graphml(graph: &KbGraph) -> String {
+    let mut lines = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#.to_string(),
+        r#"  <key id="title" for="node" attr.name="title" attr.type="string"/>"#.to_string(),
+        r#"  <key id="type" for="node" attr.name="type" attr.type="string"/>"#.to_string(),
+        r#"  <key id="tags" for="node" attr.name="tags" attr.type="string"/>"#.to_string(),
+        r#"  <key id="kind" for="edge" attr.name="kind" attr.type="string"/>"#.to_string(),
+        r#"  <graph id="kb" edgedefault="directed">"#.to_string(),
+    ];
+    for node in graph.nodes() {
+        lines.push(format!("    <node id=\"{}\">", escape_xml(&node.link)));
+        lines.push(format!(
+            "      <data key=\"title\">{}</data>",
+            escape_xml(&node.title)
+        ));
+        lines.push(format!(
+            "      <data key=\"type\">{}</data>",
+            escape_xml(&node.doc_type)
+        ));
+        lines.push(format!(
+            "      <data key=\"tags\">{}</data>",
+            escape_xml(&node.tags.join(","))
+        ));
+        lines.push("    </node>".to_string());
+    }
+    for edge in graph.edges() {
+        let kind = match edge.kind {
+            EdgeKind::Relation => "relation",
+            EdgeKind::Wikilink => "wikilink",
+        };
+        lines.push(format!(
+            "    <edge source=\"{}\" target=\"{}\"><data key=\"kind\">{kind}</data></edge>",
+            escape_xml(&edge.source),
+            escape_xml(&edge.target)
+        ));
+    }
+    lines.push("  </graph>".to_string());
+    lines.push("</graphml>".to_string());
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn escape_dot(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_mermaid(value: &str) -> String {
+    value.replace('"', "#quot;").replace('\n', " ")
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn resolve_output_path(base_dir: &Path, output: &Path) -> PathBuf {
+    if output.is_absolute() {
+        output.to_path_buf()
+    } else {
+        base_dir.join(output)
+    }
+}
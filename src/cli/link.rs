@@ -1,17 +1,15 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
 
+use super::backlinks::sync_sections;
 use super::LinkArgs;
+use crate::config::KbConfig;
 use crate::fs::{display_relative, resolve_claude_root_from_cwd, walk_kb_documents, DocumentEntry};
-use crate::model::{Document, OntologicalRelation};
+use crate::model::{normalize_relation_target, Document, DocumentFrontMatter, OntologicalRelation};
 
 pub fn run(args: LinkArgs) -> Result<()> {
-    if args.source == args.target {
-        bail!("Source and target must be different links.");
-    }
-
     let (cwd, claude_root) = resolve_claude_root_from_cwd()?;
 
     if !claude_root.exists() {
@@ -21,40 +19,53 @@ pub fn run(args: LinkArgs) -> Result<()> {
         );
     }
 
-    let mut source = load_document(&claude_root, &args.source)?;
-    let mut target = load_document(&claude_root, &args.target)?;
-
-    let added_source = insert_relation(
-        &mut source.document,
-        &target.document.front_matter.link,
-        args.force,
-    );
-    let added_target = insert_relation(
-        &mut target.document,
-        &source.document.front_matter.link,
-        args.force,
-    );
-
-    if !added_source && !added_target && !args.force {
-        println!(
+    match link_documents(&claude_root, &args.source, &args.target, args.force)? {
+        Some((source_path, target_path)) => println!(
+            "Linked {} <-> {}",
+            display_relative(&cwd, &source_path),
+            display_relative(&cwd, &target_path)
+        ),
+        None => println!(
             "Relations already existed between `{}` and `{}`; no changes made.",
             args.source, args.target
-        );
-        return Ok(());
+        ),
+    }
+
+    Ok(())
+}
+
+/// Relates `source` and `target` in both directions and returns their paths, or `None`
+/// when both relations already existed and `force` is off.
+pub(crate) fn link_documents(
+    claude_root: &Path,
+    source: &str,
+    target: &str,
+    force: bool,
+) -> Result<Option<(PathBuf, PathBuf)>> {
+    if source == target {
+        bail!("Source and target must be different links.");
+    }
+
+    let mut source = load_document(claude_root, source)?;
+    let mut target = load_document(claude_root, target)?;
+
+    let target_front = target.document.front_matter.clone();
+    let source_front = source.document.front_matter.clone();
+    let added_source = insert_relation(&mut source.document, &target_front, force);
+    let added_target = insert_relation(&mut target.document, &source_front, force);
+
+    if !added_source && !added_target && !force {
+        return Ok(None);
     }
 
-    let source_path = source.path.clone();
-    let target_path = target.path.clone();
     write_document(&source)?;
     write_document(&target)?;
 
-    println!(
-        "Linked {} <-> {}",
-        display_relative(&cwd, &source_path),
-        display_relative(&cwd, &target_path)
-    );
+    if KbConfig::load(claude_root)?.backlinks_section {
+        sync_sections(claude_root)?;
+    }
 
-    Ok(())
+    Ok(Some((source.path, target.path)))
 }
 
 fn load_document(claude_root: &Path, slug: &str) -> Result<DocumentEntry> {
@@ -86,11 +97,18 @@ fn load_document(claude_root: &Path, slug: &str) -> Result<DocumentEntry> {
     }
 }
 
-fn insert_relation(document: &mut Document, target_link: &str, force: bool) -> bool {
+pub(crate) fn insert_relation(
+    document: &mut Document,
+    target: &DocumentFrontMatter,
+    force: bool,
+) -> bool {
+    let target_link = target.link.as_str();
+    let target_uuid = target.uuid.hyphenated().to_string();
     let relations = &mut document.front_matter.ontological_relations;
-    let exists = relations
-        .iter()
-        .any(|relation| relation.relates_to == target_link);
+    let exists = relations.iter().any(|relation| {
+        let existing = normalize_relation_target(&relation.relates_to);
+        existing == target_link || existing == target_uuid
+    });
 
     if exists && !force {
         return false;
@@ -106,7 +124,7 @@ fn insert_relation(document: &mut Document, target_link: &str, force: bool) -> b
     true
 }
 
-fn write_document(record: &DocumentEntry) -> Result<()> {
+pub(crate) fn write_document(record: &DocumentEntry) -> Result<()> {
     let content = record.document.to_markdown()?;
     fs::write(&record.path, content)
         .with_context(|| format!("Unable to write {}", record.path.display()))?;
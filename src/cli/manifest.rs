@@ -1,10 +1,26 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+use similar::TextDiff;
+use uuid::Uuid;
 
-use super::ManifestArgs;
-use crate::fs::{display_relative, resolve_claude_root, walk_kb_documents, ClaudePaths};
+use super::{ManifestArgs, ManifestFormat, ManifestGrouping};
+use crate::config::KbConfig;
+use crate::fs::{
+    display_relative, is_generated_index, normalize_path, relative_link, resolve_claude_root,
+    walk_kb_documents, ClaudePaths, CLAUDE_DIRECTORIES, INDEX_FILE, INDEX_MARKER,
+};
+use crate::markdown::{escape_html, escape_table_cell, percent_encode_path, summarize};
+use crate::model::{iso8601, normalize_relation_target};
+use crate::template::Template;
+
+const SUMMARY_LENGTH: usize = 160;
 
 pub fn run(args: ManifestArgs) -> Result<()> {
     let (base_dir, claude_root) = resolve_claude_root(args.directory.as_deref())?;
@@ -17,37 +33,223 @@ pub fn run(args: ManifestArgs) -> Result<()> {
     }
 
     let layout = ClaudePaths::new(claude_root.clone());
-    let entries = collect_entries(&claude_root)?;
+    let timezone = match args.timezone {
+        Some(timezone) => timezone,
+        None => KbConfig::load(&claude_root)?
+            .display_timezone
+            .unwrap_or(Tz::UTC),
+    };
+    let entries = collect_entries(&claude_root, timezone)?;
 
-    let manifest_content = render_manifest(&claude_root, &entries)?;
-    let output_path = resolve_output_path(&base_dir, &layout, args.output.as_ref())?;
+    let output_path = resolve_output_path(&base_dir, &layout, args.output.as_ref(), args.format)?;
+    let template = match args.format {
+        ManifestFormat::Md => load_template(&base_dir, &layout, args.template.as_ref())?,
+        _ if args.template.is_some() => {
+            bail!("--template only applies to the Markdown manifest; drop it or use --format md")
+        }
+        _ => None,
+    };
+    let manifest_content = render_content(
+        &claude_root,
+        &output_path,
+        &entries,
+        args.format,
+        args.group_by,
+        args.header,
+        template,
+    )?;
 
-    if let Some(parent) = output_path.parent() {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("Unable to prepare {}", parent.display()))?;
+    let mut outputs = vec![(output_path, manifest_content)];
+    if args.type_index {
+        outputs.extend(render_type_indexes(&claude_root, &layout, &entries));
     }
 
-    fs::write(&output_path, manifest_content)
-        .with_context(|| format!("Unable to write {}", output_path.display()))?;
+    if args.check {
+        let stale = outputs
+            .iter()
+            .filter(|(path, content)| !check_manifest(&base_dir, path, content))
+            .count();
+        if stale > 0 {
+            bail!("{stale} manifest file(s) are out of date; run `kb-claude manifest` to regenerate them.");
+        }
+        return Ok(());
+    }
 
-    println!(
-        "Wrote manifest to {}",
-        display_relative(&base_dir, &output_path)
-    );
+    for (path, content) in outputs {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Unable to prepare {}", parent.display()))?;
+        }
+
+        fs::write(&path, content).with_context(|| format!("Unable to write {}", path.display()))?;
+
+        println!("Wrote manifest to {}", display_relative(&base_dir, &path));
+    }
     Ok(())
 }
 
-#[derive(Debug)]
+pub(crate) fn render_default(claude_root: &Path, format: ManifestFormat) -> Result<String> {
+    let layout = ClaudePaths::new(claude_root);
+    let timezone = KbConfig::load(claude_root)?
+        .display_timezone
+        .unwrap_or(Tz::UTC);
+    let entries = collect_entries(claude_root, timezone)?;
+    let output_path = layout.manifest_path().with_extension(format.extension());
+    let template = match format {
+        ManifestFormat::Md => load_template(claude_root, &layout, None)?,
+        _ => None,
+    };
+    render_content(
+        claude_root,
+        &output_path,
+        &entries,
+        format,
+        ManifestGrouping::None,
+        false,
+        template,
+    )
+}
+
+fn render_content(
+    claude_root: &Path,
+    output_path: &Path,
+    entries: &[ManifestEntry],
+    format: ManifestFormat,
+    group_by: ManifestGrouping,
+    header: bool,
+    template: Option<Template>,
+) -> Result<String> {
+    Ok(match format {
+        ManifestFormat::Md => {
+            let table = render_manifest(claude_root, output_path, entries, group_by, header);
+            match template {
+                Some(template) => template.render(&template_context(output_path, entries, table)),
+                None => table,
+            }
+        }
+        ManifestFormat::Json => serde_json::to_string_pretty(&entries)? + "\n",
+        ManifestFormat::Yaml => serde_yaml::to_string(&entries)?,
+        ManifestFormat::Csv => render_csv(entries),
+        ManifestFormat::Html => render_html(claude_root, output_path, entries),
+    })
+}
+
+fn load_template(
+    base_dir: &Path,
+    layout: &ClaudePaths,
+    override_path: Option<&PathBuf>,
+) -> Result<Option<Template>> {
+    let path = match override_path {
+        Some(custom) => base_dir.join(custom),
+        None => layout.manifest_template_path(),
+    };
+    if override_path.is_none() && !path.exists() {
+        return Ok(None);
+    }
+
+    let source = fs::read_to_string(&path)
+        .with_context(|| format!("Unable to read template {}", path.display()))?;
+    Template::parse(&source)
+        .map(Some)
+        .with_context(|| format!("Invalid template {}", path.display()))
+}
+
+fn template_context(output_path: &Path, entries: &[ManifestEntry], table: String) -> Value {
+    let output_path = normalize_path(output_path);
+    let entry_value = |entry: &ManifestEntry| {
+        json!({
+            "title": entry.title,
+            "link": entry.link,
+            "type": entry.doc_type,
+            "uuid": entry.uuid.hyphenated().to_string(),
+            "path": format_path(Path::new(""), &entry.relative_path),
+            "href": percent_encode_path(&relative_link(&output_path, &entry.path)),
+            "summary": entry.summary,
+            "tags": entry.tags,
+            "relations": entry.relations,
+            "created": entry.created_date.to_string(),
+            "updated": entry.updated_date.to_string(),
+        })
+    };
+
+    let all: Vec<&ManifestEntry> = entries.iter().collect();
+    let types: Vec<Value> = group_by_type(&all)
+        .into_iter()
+        .map(|(name, members)| {
+            json!({
+                "name": name,
+                "count": members.len(),
+                "entries": members.into_iter().map(entry_value).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    json!({
+        "count": entries.len(),
+        "updated": entries
+            .iter()
+            .map(|entry| entry.updated_date)
+            .max()
+            .map(|updated| updated.to_string()),
+        "table": table.trim_end(),
+        "entries": entries.iter().map(entry_value).collect::<Vec<_>>(),
+        "types": types,
+    })
+}
+
+fn check_manifest(base_dir: &Path, output_path: &Path, rendered: &str) -> bool {
+    let display = display_relative(base_dir, output_path);
+    let existing = fs::read_to_string(output_path).unwrap_or_default();
+
+    if existing == rendered {
+        println!("Manifest {display} is up to date.");
+        return true;
+    }
+
+    let diff = TextDiff::from_lines(existing.as_str(), rendered);
+    let name = display.trim_start_matches("./");
+    print!(
+        "{}",
+        diff.unified_diff()
+            .context_radius(3)
+            .header(&format!("a/{name}"), &format!("b/{name}"))
+    );
+    false
+}
+
+#[derive(Debug, Serialize)]
 struct ManifestEntry {
     title: String,
+    link: String,
+    #[serde(rename = "type")]
     doc_type: String,
+    uuid: Uuid,
+    #[serde(rename = "path", serialize_with = "serialize_path")]
     relative_path: PathBuf,
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
     tags: Vec<String>,
     relations: Vec<String>,
-    updated_at: chrono::NaiveDate,
+    #[serde(with = "iso8601")]
+    created_at: DateTime<Utc>,
+    #[serde(with = "iso8601")]
+    updated_at: DateTime<Utc>,
+    #[serde(skip)]
+    created_date: NaiveDate,
+    #[serde(skip)]
+    updated_date: NaiveDate,
 }
 
-fn collect_entries(claude_root: &Path) -> Result<Vec<ManifestEntry>> {
+fn serialize_path<S>(path: &Path, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&format_path(Path::new(""), path))
+}
+
+fn collect_entries(claude_root: &Path, timezone: Tz) -> Result<Vec<ManifestEntry>> {
     let mut entries = Vec::new();
 
     for entry_result in walk_kb_documents(claude_root) {
@@ -58,49 +260,399 @@ fn collect_entries(claude_root: &Path) -> Result<Vec<ManifestEntry>> {
             .unwrap_or(&entry.path)
             .to_path_buf();
 
+        let front = &entry.document.front_matter;
         entries.push(ManifestEntry {
-            title: entry.document.front_matter.title.clone(),
-            doc_type: entry.document.front_matter.doc_type.clone(),
+            title: front.title.clone(),
+            link: front.link.clone(),
+            doc_type: front.doc_type.clone(),
+            uuid: front.uuid,
             relative_path: relative,
-            tags: entry.document.front_matter.tags.clone(),
-            relations: entry
-                .document
-                .front_matter
+            path: entry.path.clone(),
+            summary: front
+                .summary
+                .clone()
+                .or_else(|| summarize(&entry.document.body, SUMMARY_LENGTH)),
+            tags: front.tags.clone(),
+            relations: front
                 .ontological_relations
                 .iter()
-                .map(|relation| relation.relates_to.clone())
+                .map(|relation| normalize_relation_target(&relation.relates_to))
                 .collect(),
-            updated_at: entry.document.front_matter.updated_at.date_naive(),
+            created_at: front.created_at,
+            updated_at: front.last_updated(),
+            created_date: front.created_at.with_timezone(&timezone).date_naive(),
+            updated_date: front.last_updated().with_timezone(&timezone).date_naive(),
         });
     }
 
-    entries.sort_by(|a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase()));
+    entries.sort_by(|left, right| {
+        left.title
+            .to_lowercase()
+            .cmp(&right.title.to_lowercase())
+            .then_with(|| left.link.cmp(&right.link))
+            .then_with(|| left.uuid.cmp(&right.uuid))
+            .then_with(|| left.relative_path.cmp(&right.relative_path))
+    });
     Ok(entries)
 }
 
-fn render_manifest(claude_root: &Path, entries: &[ManifestEntry]) -> Result<String> {
+fn render_manifest(
+    claude_root: &Path,
+    output_path: &Path,
+    entries: &[ManifestEntry],
+    group_by: ManifestGrouping,
+    header: bool,
+) -> String {
+    let table = MarkdownTable::new(claude_root, output_path, entries);
     let mut lines = Vec::new();
-    lines.push("| Title | Type | Path | Tags | Relations | Updated |".to_string());
-    lines.push("|-------|------|------|------|-----------|---------|".to_string());
 
-    for entry in entries {
-        let path_display = format_path(claude_root, &entry.relative_path);
-        let tags = format_list(&entry.tags);
-        let relations = format_list(&entry.relations);
-        let updated = entry.updated_at.to_string();
+    if header {
+        lines.extend(render_header(entries));
+    }
 
+    let all: Vec<&ManifestEntry> = entries.iter().collect();
+    let groups: Vec<(String, Vec<&ManifestEntry>)> = match group_by {
+        ManifestGrouping::None => {
+            lines.extend(table.render(&all));
+            lines.push(String::new());
+            return lines.join("\n");
+        }
+        ManifestGrouping::Type => group_by_type(&all),
+        ManifestGrouping::Tag => {
+            let mut by_tag: BTreeMap<String, Vec<&ManifestEntry>> = BTreeMap::new();
+            let mut untagged = Vec::new();
+            for entry in &all {
+                if entry.tags.is_empty() {
+                    untagged.push(*entry);
+                }
+                for tag in &entry.tags {
+                    by_tag.entry(tag.clone()).or_default().push(*entry);
+                }
+            }
+            let mut groups: Vec<_> = by_tag.into_iter().collect();
+            if !untagged.is_empty() {
+                groups.push(("(untagged)".to_string(), untagged));
+            }
+            groups
+        }
+        ManifestGrouping::Month => {
+            let mut by_month: BTreeMap<String, Vec<&ManifestEntry>> = BTreeMap::new();
+            for entry in &all {
+                by_month
+                    .entry(entry.updated_date.format("%Y-%m").to_string())
+                    .or_default()
+                    .push(*entry);
+            }
+            by_month.into_iter().rev().collect()
+        }
+    };
+
+    if groups.is_empty() {
+        lines.extend(table.render(&[]));
+    }
+    for (heading, members) in groups {
         lines.push(format!(
-            "| {} | {} | {} | {} | {} | {} |",
-            entry.title, entry.doc_type, path_display, tags, relations, updated
+            "## {} ({})",
+            escape_table_cell(&heading),
+            members.len()
         ));
+        lines.push(String::new());
+        lines.extend(table.render(&members));
+        lines.push(String::new());
     }
 
-    if entries.is_empty() {
-        lines.push("| *(empty)* | - | - | - | - | - |".to_string());
+    if lines.last().is_some_and(|line| !line.is_empty()) {
+        lines.push(String::new());
+    }
+    lines.join("\n")
+}
+
+fn group_by_type<'a>(entries: &[&'a ManifestEntry]) -> Vec<(String, Vec<&'a ManifestEntry>)> {
+    let mut by_type: BTreeMap<&str, Vec<&ManifestEntry>> = BTreeMap::new();
+    for entry in entries {
+        by_type
+            .entry(entry.doc_type.as_str())
+            .or_default()
+            .push(*entry);
+    }
+
+    let mut groups = Vec::new();
+    for doc_type in CLAUDE_DIRECTORIES {
+        if let Some(members) = by_type.remove(doc_type) {
+            groups.push((doc_type.to_string(), members));
+        }
+    }
+    groups.extend(
+        by_type
+            .into_iter()
+            .map(|(doc_type, members)| (doc_type.to_string(), members)),
+    );
+    groups
+}
+
+fn render_header(entries: &[ManifestEntry]) -> Vec<String> {
+    let all: Vec<&ManifestEntry> = entries.iter().collect();
+    let by_type = group_by_type(&all)
+        .into_iter()
+        .map(|(doc_type, members)| format!("{doc_type} {}", members.len()))
+        .collect::<Vec<_>>();
+    let tags: BTreeSet<&str> = entries
+        .iter()
+        .flat_map(|entry| entry.tags.iter().map(String::as_str))
+        .collect();
+    let last_updated = entries
+        .iter()
+        .map(|entry| entry.updated_date)
+        .max()
+        .map(|updated| updated.to_string())
+        .unwrap_or_else(|| "—".to_string());
+
+    vec![
+        "# Knowledge Base Manifest".to_string(),
+        String::new(),
+        format!("- Documents: {}", entries.len()),
+        format!("- By type: {}", format_list(&by_type)),
+        format!("- Tags: {}", tags.len()),
+        format!("- Last updated: {last_updated}"),
+        String::new(),
+    ]
+}
+
+/// One `index.md` per type directory that holds documents, listing just that type. Directories
+/// whose `index.md` is a real document are skipped so it is never overwritten.
+fn render_type_indexes(
+    claude_root: &Path,
+    layout: &ClaudePaths,
+    entries: &[ManifestEntry],
+) -> Vec<(PathBuf, String)> {
+    let all: Vec<&ManifestEntry> = entries.iter().collect();
+    group_by_type(&all)
+        .into_iter()
+        .filter(|(doc_type, _)| layout.is_supported_type(doc_type))
+        .filter_map(|(doc_type, members)| {
+            let path = layout.type_directory(&doc_type).join(INDEX_FILE);
+            if path.exists() && !is_generated_index(&path) {
+                println!(
+                    "Warning: skipping the {doc_type} index; {} is a document, not a generated index.",
+                    display_relative(claude_root.parent().unwrap_or(claude_root), &path)
+                );
+                return None;
+            }
+            let table = MarkdownTable::new(claude_root, &path, entries);
+            let mut lines = vec![
+                INDEX_MARKER.to_string(),
+                format!("# {doc_type} ({})", members.len()),
+                String::new(),
+            ];
+            lines.extend(table.render(&members));
+            lines.push(String::new());
+            Some((path, lines.join("\n")))
+        })
+        .collect()
+}
+
+struct ManifestLinks<'a> {
+    output_path: PathBuf,
+    targets: HashMap<String, &'a Path>,
+}
+
+impl<'a> ManifestLinks<'a> {
+    fn new(output_path: &Path, entries: &'a [ManifestEntry]) -> Self {
+        let mut targets: HashMap<String, &Path> = HashMap::new();
+        for entry in entries {
+            targets.insert(entry.uuid.hyphenated().to_string(), &entry.path);
+            targets.insert(entry.link.clone(), &entry.path);
+        }
+        Self {
+            output_path: normalize_path(output_path),
+            targets,
+        }
+    }
+
+    fn href(&self, target: &Path) -> String {
+        percent_encode_path(&relative_link(&self.output_path, target))
+    }
+
+    fn relation_target(&self, relation: &str) -> Option<&'a Path> {
+        self.targets.get(relation).copied()
+    }
+}
+
+struct MarkdownTable<'a> {
+    claude_root: &'a Path,
+    links: ManifestLinks<'a>,
+}
+
+impl<'a> MarkdownTable<'a> {
+    fn new(claude_root: &'a Path, output_path: &Path, entries: &'a [ManifestEntry]) -> Self {
+        Self {
+            claude_root,
+            links: ManifestLinks::new(output_path, entries),
+        }
+    }
+
+    fn link_to(&self, text: &str, target: &Path) -> String {
+        format!("[{}]({})", escape_table_cell(text), self.links.href(target))
+    }
+
+    fn render(&self, entries: &[&ManifestEntry]) -> Vec<String> {
+        let mut lines = Vec::new();
+        lines.push("| Title | Type | Path | Tags | Relations | Updated | Summary |".to_string());
+        lines.push("|-------|------|------|------|-----------|---------|---------|".to_string());
+
+        for entry in entries {
+            let path_display = self.link_to(
+                &format_path(self.claude_root, &entry.relative_path),
+                &entry.path,
+            );
+            let tags = format_list(
+                &entry
+                    .tags
+                    .iter()
+                    .map(|tag| escape_table_cell(tag))
+                    .collect::<Vec<_>>(),
+            );
+            let relations = format_list(
+                &entry
+                    .relations
+                    .iter()
+                    .map(|relation| match self.links.relation_target(relation) {
+                        Some(target) => self.link_to(relation, target),
+                        None => escape_table_cell(relation),
+                    })
+                    .collect::<Vec<_>>(),
+            );
+            let updated = entry.updated_date.to_string();
+
+            let summary = entry
+                .summary
+                .as_deref()
+                .map(escape_table_cell)
+                .unwrap_or_else(|| "—".to_string());
+
+            lines.push(format!(
+                "| {} | {} | {} | {} | {} | {} | {} |",
+                escape_table_cell(&entry.title),
+                escape_table_cell(&entry.doc_type),
+                path_display,
+                tags,
+                relations,
+                updated,
+                summary
+            ));
+        }
+
+        if entries.is_empty() {
+            lines.push("| *(empty)* | - | - | - | - | - | - |".to_string());
+        }
+
+        lines
+    }
+}
+
+const CSV_HEADER: &[&str] = &[
+    "title",
+    "link",
+    "type",
+    "uuid",
+    "path",
+    "tags",
+    "relations",
+    "summary",
+    "created_at",
+    "updated_at",
+];
+
+fn render_csv(entries: &[ManifestEntry]) -> String {
+    let mut lines = vec![CSV_HEADER.join(",")];
+
+    for entry in entries {
+        let fields = [
+            entry.title.clone(),
+            entry.link.clone(),
+            entry.doc_type.clone(),
+            entry.uuid.to_string(),
+            format_path(Path::new(""), &entry.relative_path),
+            entry.tags.join(";"),
+            entry.relations.join(";"),
+            entry.summary.clone().unwrap_or_default(),
+            iso8601::format(&entry.created_at),
+            iso8601::format(&entry.updated_at),
+        ];
+        lines.push(
+            fields
+                .iter()
+                .map(|field| csv_field(field))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
     }
 
     lines.push(String::new());
-    Ok(lines.join("\n"))
+    lines.join("\n")
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_html(claude_root: &Path, output_path: &Path, entries: &[ManifestEntry]) -> String {
+    let links = ManifestLinks::new(output_path, entries);
+    let anchor = |text: &str, target: &Path| {
+        format!(
+            "<a href=\"{}\">{}</a>",
+            escape_html(&links.href(target)),
+            escape_html(text)
+        )
+    };
+    let mut lines = vec![
+        "<!DOCTYPE html>".to_string(),
+        "<html>".to_string(),
+        "<head>".to_string(),
+        "<meta charset=\"utf-8\">".to_string(),
+        "<title>Knowledge base manifest</title>".to_string(),
+        "</head>".to_string(),
+        "<body>".to_string(),
+        "<table>".to_string(),
+        "<thead>".to_string(),
+        "<tr><th>Title</th><th>Type</th><th>Path</th><th>Tags</th><th>Relations</th><th>Updated</th><th>Summary</th></tr>"
+            .to_string(),
+        "</thead>".to_string(),
+        "<tbody>".to_string(),
+    ];
+
+    for entry in entries {
+        let path = anchor(&format_path(claude_root, &entry.relative_path), &entry.path);
+        let relations = entry
+            .relations
+            .iter()
+            .map(|relation| match links.relation_target(relation) {
+                Some(target) => anchor(relation, target),
+                None => escape_html(relation),
+            })
+            .collect::<Vec<_>>();
+        lines.push(format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_html(&entry.title),
+            escape_html(&entry.doc_type),
+            path,
+            escape_html(&format_list(&entry.tags)),
+            format_list(&relations),
+            entry.updated_date,
+            escape_html(entry.summary.as_deref().unwrap_or_default())
+        ));
+    }
+
+    lines.extend(
+        ["</tbody>", "</table>", "</body>", "</html>", ""]
+            .iter()
+            .map(ToString::to_string),
+    );
+    lines.join("\n")
 }
 
 fn format_path(claude_root: &Path, relative_path: &Path) -> String {
@@ -127,6 +679,7 @@ fn resolve_output_path(
     cwd: &Path,
     layout: &ClaudePaths,
     override_path: Option<&PathBuf>,
+    format: ManifestFormat,
 ) -> Result<PathBuf> {
     if let Some(custom) = override_path {
         if custom.is_absolute() {
@@ -135,5 +688,5 @@ fn resolve_output_path(
         return Ok(cwd.join(custom));
     }
 
-    Ok(layout.manifest_path())
+    Ok(layout.manifest_path().with_extension(format.extension()))
 }
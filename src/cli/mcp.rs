@@ -0,0 +1,529 @@
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::backlinks::sync_sections;
+use super::link::{insert_relation, link_documents, write_document};
+use super::new::{
+    describe_duplicates, normalize_tags, plan_new_document, validate_type, write_new_document,
+    NewDocumentOptions, NewDocumentPlan,
+};
+use super::search::search;
+use super::serve::document_payload;
+use super::validate::{collect_findings, failure_summary, format_finding};
+use super::{Disambiguation, McpArgs};
+use crate::config::KbConfig;
+use crate::fs::{
+    display_relative, resolve_claude_root, walk_kb_documents, ClaudePaths, DocumentEntry,
+    CLAUDE_DIRECTORIES,
+};
+use crate::graph::KbGraph;
+use crate::model::{normalize_relation_target, Document, DocumentFrontMatter, OntologicalRelation};
+
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+const RESOURCE_SCHEME: &str = "kb://";
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
+/// Serves the Model Context Protocol over stdio: one JSON-RPC message per line in, one
+/// response per line out. Nothing else may be written to stdout.
+pub fn run(args: McpArgs) -> Result<()> {
+    let (base_dir, claude_root) = resolve_claude_root(args.directory.as_deref())?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            base_dir.display()
+        );
+    }
+
+    let stdin = io::stdin();
+    let mut stdout = io::stdout().lock();
+    for line in stdin.lock().lines() {
+        let line = line.context("Unable to read from stdin")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle_message(&claude_root, message),
+            Err(error) => Some(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, format!("Invalid JSON: {error}")),
+            )),
+        };
+
+        if let Some(response) = response {
+            serde_json::to_writer(&mut stdout, &response)?;
+            stdout.write_all(b"\n")?;
+            stdout.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+fn handle_message(claude_root: &Path, message: Value) -> Option<Value> {
+    let Some(object) = message.as_object() else {
+        return Some(error_response(
+            Value::Null,
+            RpcError::new(INVALID_REQUEST, "Expected a JSON-RPC object"),
+        ));
+    };
+    let id = object.get("id").cloned()?;
+    let method = object.get("method").and_then(Value::as_str)?;
+    let params = object.get("params").cloned().unwrap_or(Value::Null);
+
+    Some(match dispatch(claude_root, method, &params) {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => error_response(id, error),
+    })
+}
+
+fn dispatch(claude_root: &Path, method: &str, params: &Value) -> Result<Value, RpcError> {
+    match method {
+        "initialize" => Ok(initialize(params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => call_tool(claude_root, params),
+        "resources/list" => list_resources(claude_root),
+        "resources/read" => read_resource(claude_root, params),
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method `{method}`"),
+        )),
+    }
+}
+
+fn initialize(params: &Value) -> Value {
+    let requested = params
+        .get("protocolVersion")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let version = PROTOCOL_VERSIONS
+        .iter()
+        .find(|version| **version == requested)
+        .unwrap_or(&PROTOCOL_VERSIONS[0]);
+
+    json!({
+        "protocolVersion": version,
+        "capabilities": {
+            "tools": { "listChanged": false },
+            "resources": { "listChanged": false, "subscribe": false },
+        },
+        "serverInfo": { "name": "kb-claude", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "Search the knowledge base before creating entries, link related \
+                         entries, and run validate after edits.",
+    })
+}
+
+fn tool_definitions() -> Value {
+    let string_list = json!({ "type": "array", "items": { "type": "string" } });
+    json!([
+        {
+            "name": "search",
+            "description": "Find documents containing every term, best match first.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Space-separated search terms" },
+                    "tags": string_list.clone(),
+                },
+                "required": ["query"],
+            },
+        },
+        {
+            "name": "get",
+            "description": "Fetch one document's front matter, body and graph neighbours by link, \
+                            [[link]] or uuid.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "link": { "type": "string" } },
+                "required": ["link"],
+            },
+        },
+        {
+            "name": "new",
+            "description": "Create a document. Fails when the title looks like a duplicate unless \
+                            allow_duplicate is set.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "title": { "type": "string" },
+                    "type": { "type": "string", "enum": CLAUDE_DIRECTORIES },
+                    "body": { "type": "string" },
+                    "summary": { "type": "string" },
+                    "tags": string_list.clone(),
+                    "relates_to": string_list,
+                    "allow_duplicate": { "type": "boolean" },
+                    "disambiguate": { "type": "string", "enum": ["number", "uuid"] },
+                },
+                "required": ["title", "type"],
+            },
+        },
+        {
+            "name": "link",
+            "description": "Relate two documents in both directions.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "source": { "type": "string" },
+                    "target": { "type": "string" },
+                    "force": { "type": "boolean" },
+                },
+                "required": ["source", "target"],
+            },
+        },
+        {
+            "name": "validate",
+            "description": "Check metadata, links and anchors; strict also fails on warnings.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "strict": { "type": "boolean" } },
+            },
+        },
+    ])
+}
+
+#[derive(Deserialize)]
+struct SearchParams {
+    query: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct GetParams {
+    link: String,
+}
+
+#[derive(Deserialize)]
+struct NewParams {
+    title: String,
+    #[serde(rename = "type")]
+    doc_type: String,
+    #[serde(default)]
+    body: String,
+    summary: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    relates_to: Vec<String>,
+    #[serde(default)]
+    allow_duplicate: bool,
+    disambiguate: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LinkParams {
+    source: String,
+    target: String,
+    #[serde(default)]
+    force: bool,
+}
+
+#[derive(Deserialize)]
+struct ValidateParams {
+    #[serde(default)]
+    strict: bool,
+}
+
+/// Runs a tool. Failures inside the tool come back as an `isError` result so the agent can
+/// read them; only an unknown tool is a protocol error.
+fn call_tool(claude_root: &Path, params: &Value) -> Result<Value, RpcError> {
+    let name = params
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing tool name"))?;
+    let arguments = match params.get("arguments") {
+        None | Some(Value::Null) => json!({}),
+        Some(arguments) => arguments.clone(),
+    };
+
+    let outcome = match name {
+        "search" => arguments_as(arguments).and_then(|args| search_tool(claude_root, args)),
+        "get" => arguments_as(arguments).and_then(|args| get_tool(claude_root, args)),
+        "new" => arguments_as(arguments).and_then(|args| new_tool(claude_root, args)),
+        "link" => arguments_as(arguments).and_then(|args| link_tool(claude_root, args)),
+        "validate" => arguments_as(arguments).and_then(|args| validate_tool(claude_root, args)),
+        _ => {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                format!("Unknown tool `{name}`"),
+            ))
+        }
+    };
+
+    let (text, is_error) = match outcome {
+        Ok(ToolOutput::Success(text)) => (text, false),
+        Ok(ToolOutput::Failure(text)) => (text, true),
+        Err(error) => (format!("{error:#}"), true),
+    };
+    Ok(json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    }))
+}
+
+enum ToolOutput {
+    Success(String),
+    Failure(String),
+}
+
+fn arguments_as<T: for<'de> Deserialize<'de>>(arguments: Value) -> Result<T> {
+    serde_json::from_value(arguments).context("Invalid tool arguments")
+}
+
+fn pretty(value: &Value) -> Result<ToolOutput> {
+    Ok(ToolOutput::Success(serde_json::to_string_pretty(value)?))
+}
+
+fn search_tool(claude_root: &Path, args: SearchParams) -> Result<ToolOutput> {
+    let config = KbConfig::load(claude_root)?;
+    let entries = walk_kb_documents(claude_root).collect::<Result<Vec<_>>>()?;
+    let terms: Vec<String> = args.query.split_whitespace().map(str::to_string).collect();
+    let matches = search(
+        claude_root,
+        &entries,
+        &terms,
+        &args.tags,
+        config.slug_policy,
+    );
+    pretty(&json!(matches))
+}
+
+fn get_tool(claude_root: &Path, args: GetParams) -> Result<ToolOutput> {
+    let config = KbConfig::load(claude_root)?;
+    let entries = walk_kb_documents(claude_root).collect::<Result<Vec<_>>>()?;
+    let graph = KbGraph::from_entries(&entries, config.slug_policy);
+    let entry = graph
+        .resolve(&args.link)
+        .and_then(|link| {
+            entries
+                .iter()
+                .find(|entry| entry.document.front_matter.link == link)
+        })
+        .ok_or_else(|| anyhow!("No document found for `{}`.", args.link))?;
+    pretty(&document_payload(claude_root, &graph, entry))
+}
+
+fn new_tool(claude_root: &Path, args: NewParams) -> Result<ToolOutput> {
+    let workspace = claude_root.parent().unwrap_or(claude_root);
+    let layout = ClaudePaths::new(claude_root);
+    let config = KbConfig::load(claude_root)?;
+    validate_type(&layout, &args.doc_type)?;
+    let strategy = args
+        .disambiguate
+        .as_deref()
+        .map(|value| Disambiguation::from_str(value, true).map_err(|error| anyhow!(error)))
+        .transpose()?;
+
+    let plan = plan_new_document(
+        claude_root,
+        &config,
+        DocumentFrontMatter::new(&args.title, args.doc_type),
+        &NewDocumentOptions {
+            file_override: None,
+            disambiguate: strategy,
+            allow_duplicate: args.allow_duplicate,
+        },
+        |duplicates| {
+            let mut lines = describe_duplicates(workspace, &args.title, duplicates);
+            lines.push(
+                "Update the existing document, or set allow_duplicate to create it anyway."
+                    .to_string(),
+            );
+            bail!("{}", lines.join("\n"))
+        },
+    )?;
+
+    let NewDocumentPlan {
+        mut front_matter,
+        output_path,
+        link_taken,
+        ..
+    } = plan;
+    front_matter.tags = normalize_tags(args.tags.iter().map(String::as_str));
+    front_matter.summary = args.summary;
+    front_matter.ontological_relations = args
+        .relates_to
+        .iter()
+        .map(|link| OntologicalRelation {
+            relates_to: normalize_relation_target(link),
+        })
+        .collect();
+
+    let mut entries = walk_kb_documents(claude_root).collect::<Result<Vec<_>>>()?;
+    let graph = KbGraph::from_entries(&entries, config.slug_policy);
+    let mut targets: Vec<usize> = Vec::new();
+    for relates_to in &args.relates_to {
+        let index = graph.resolve(relates_to).and_then(|target| {
+            entries
+                .iter()
+                .position(|entry| entry.document.front_matter.link == target)
+        });
+        if let Some(index) = index.filter(|index| !targets.contains(index)) {
+            targets.push(index);
+        }
+    }
+
+    let link = front_matter.link.clone();
+    let document = Document::new(front_matter, args.body);
+    write_new_document(claude_root, &config, &output_path, &document)?;
+
+    let mut updated_targets = false;
+    for index in targets {
+        let target = &mut entries[index];
+        if insert_relation(&mut target.document, &document.front_matter, false) {
+            write_document(target)?;
+            updated_targets = true;
+        }
+    }
+    if updated_targets && config.backlinks_section {
+        sync_sections(claude_root)?;
+    }
+
+    let mut created = json!({
+        "link": link,
+        "path": display_relative(workspace, &output_path),
+    });
+    if link_taken {
+        created["warning"] = json!(format!(
+            "Link `{link}` is already used by another document; set disambiguate to keep links unique."
+        ));
+    }
+    pretty(&created)
+}
+
+fn link_tool(claude_root: &Path, args: LinkParams) -> Result<ToolOutput> {
+    let workspace = claude_root.parent().unwrap_or(claude_root);
+    let text = match link_documents(claude_root, &args.source, &args.target, args.force)? {
+        Some((source_path, target_path)) => format!(
+            "Linked {} <-> {}",
+            display_relative(workspace, &source_path),
+            display_relative(workspace, &target_path)
+        ),
+        None => format!(
+            "Relations already existed between `{}` and `{}`; no changes made.",
+            args.source, args.target
+        ),
+    };
+    Ok(ToolOutput::Success(text))
+}
+
+fn validate_tool(claude_root: &Path, args: ValidateParams) -> Result<ToolOutput> {
+    let workspace = claude_root.parent().unwrap_or(claude_root);
+    let layout = ClaudePaths::new(claude_root);
+    let config = KbConfig::load(claude_root)?;
+    let findings = collect_findings(claude_root, &layout, &config)?;
+
+    let mut lines: Vec<String> = findings
+        .iter()
+        .map(|finding| format_finding(finding, workspace))
+        .collect();
+    match failure_summary(&findings, args.strict) {
+        Some(failure) => {
+            lines.push(failure);
+            Ok(ToolOutput::Failure(lines.join("\n")))
+        }
+        None => {
+            lines.push(format!(
+                "Validation passed with {} finding(s).",
+                findings.len()
+            ));
+            Ok(ToolOutput::Success(lines.join("\n")))
+        }
+    }
+}
+
+fn list_resources(claude_root: &Path) -> Result<Value, RpcError> {
+    let entries = load_entries(claude_root)?;
+    let resources: Vec<Value> = entries
+        .iter()
+        .map(|entry| {
+            let front = &entry.document.front_matter;
+            let mut resource = json!({
+                "uri": format!("{RESOURCE_SCHEME}{}", front.link),
+                "name": front.link,
+                "title": front.title,
+                "mimeType": "text/markdown",
+            });
+            if let Some(summary) = &front.summary {
+                resource["description"] = json!(summary);
+            }
+            resource
+        })
+        .collect();
+    Ok(json!({ "resources": resources }))
+}
+
+fn read_resource(claude_root: &Path, params: &Value) -> Result<Value, RpcError> {
+    let uri = params
+        .get("uri")
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing resource uri"))?;
+    let not_found = || RpcError::new(RESOURCE_NOT_FOUND, format!("Resource not found: {uri}"));
+    let reference = uri.strip_prefix(RESOURCE_SCHEME).ok_or_else(not_found)?;
+
+    let config = KbConfig::load(claude_root)
+        .map_err(|error| RpcError::new(INTERNAL_ERROR, format!("{error:#}")))?;
+    let entries = load_entries(claude_root)?;
+    let graph = KbGraph::from_entries(&entries, config.slug_policy);
+    let entry = graph
+        .resolve(reference)
+        .and_then(|link| {
+            entries
+                .iter()
+                .find(|entry| entry.document.front_matter.link == link)
+        })
+        .ok_or_else(not_found)?;
+    let text = fs::read_to_string(&entry.path).map_err(|error| {
+        RpcError::new(
+            RESOURCE_NOT_FOUND,
+            format!("Unable to read {}: {error}", entry.path.display()),
+        )
+    })?;
+
+    Ok(json!({
+        "contents": [{ "uri": uri, "mimeType": "text/markdown", "text": text }],
+    }))
+}
+
+fn load_entries(claude_root: &Path) -> Result<Vec<DocumentEntry>, RpcError> {
+    walk_kb_documents(claude_root)
+        .collect::<Result<Vec<_>>>()
+        .map_err(|error| RpcError::new(INTERNAL_ERROR, format!("{error:#}")))
+}
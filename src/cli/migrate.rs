@@ -0,0 +1,82 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use super::MigrateArgs;
+use crate::fs::{display_relative, resolve_claude_root, walk_kb_files};
+use crate::model::CURRENT_SCHEMA_VERSION;
+use crate::schema::migrate_document;
+
+pub fn run(args: MigrateArgs) -> Result<()> {
+    let (target_dir, claude_root) = resolve_claude_root(args.directory.as_deref())?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            target_dir.display()
+        );
+    }
+
+    let workspace = claude_root
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| target_dir.to_path_buf());
+
+    let mut migrated = 0;
+    let mut failures = 0;
+
+    for path_result in walk_kb_files(&claude_root) {
+        let path = path_result?;
+        let display = display_relative(&workspace, &path);
+
+        let raw = fs::read_to_string(&path)
+            .with_context(|| format!("Unable to read {}", path.display()))?;
+        let outcome = match migrate_document(&raw) {
+            Ok(outcome) => outcome,
+            Err(error) => {
+                println!("error: {display} — {error:#}");
+                failures += 1;
+                continue;
+            }
+        };
+
+        if !outcome.is_changed() {
+            continue;
+        }
+
+        migrated += 1;
+        let verb = if args.dry_run {
+            "Would migrate"
+        } else {
+            "Migrated"
+        };
+        println!(
+            "{verb} {display} (v{} → v{})",
+            outcome.from_version, outcome.to_version
+        );
+        for change in &outcome.changes {
+            println!("  - {change}");
+        }
+
+        if !args.dry_run {
+            let content = outcome.document.to_markdown()?;
+            fs::write(&path, content)
+                .with_context(|| format!("Unable to write {}", path.display()))?;
+        }
+    }
+
+    if failures > 0 {
+        bail!("Migration failed for {failures} document(s).");
+    }
+
+    if migrated == 0 {
+        println!("All documents already at schema version {CURRENT_SCHEMA_VERSION}.");
+    } else if args.dry_run {
+        println!("Dry run: {migrated} document(s) would be migrated.");
+    } else {
+        println!("Migrated {migrated} document(s) to schema version {CURRENT_SCHEMA_VERSION}.");
+    }
+
+    Ok(())
+}
@@ -1,13 +1,31 @@
 use std::path::PathBuf;
 
 use anyhow::Result;
-use clap::{ArgAction, Args, Parser, Subcommand};
+use chrono_tz::Tz;
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
 
+use crate::similarity::DUPLICATE_THRESHOLD;
+
+mod backlinks;
+mod dupes;
+mod export;
+mod fmt;
+mod graph;
 mod init;
 mod link;
 mod manifest;
+mod mcp;
+mod migrate;
+mod mv;
 mod new;
+mod orphans;
+mod path;
+mod related;
 mod search;
+mod serve;
+mod stats;
+mod suggest_links;
+mod toc;
 mod validate;
 
 #[derive(Parser, Debug)]
@@ -31,6 +49,21 @@ pub enum Command {
     Link(LinkArgs),
     Validate(ValidateArgs),
     Manifest(ManifestArgs),
+    Migrate(MigrateArgs),
+    Fmt(FmtArgs),
+    Graph(GraphArgs),
+    Related(RelatedArgs),
+    Path(PathArgs),
+    Backlinks(BacklinksArgs),
+    Orphans(OrphansArgs),
+    Stats(StatsArgs),
+    Mv(MvArgs),
+    Toc(TocArgs),
+    SuggestLinks(SuggestLinksArgs),
+    Dupes(DupesArgs),
+    Export(ExportArgs),
+    Serve(ServeArgs),
+    Mcp(McpArgs),
 }
 
 #[derive(Args, Debug, Clone)]
@@ -70,6 +103,12 @@ pub struct NewArgs {
         help = "Tag to attach; use multiple times for more than one tag"
     )]
     pub tags: Vec<String>,
+    #[arg(
+        long,
+        value_name = "TEXT",
+        help = "One-line summary shown in the manifest instead of an extract from the body"
+    )]
+    pub summary: Option<String>,
     #[arg(
         long = "relates-to",
         value_name = "LINK",
@@ -84,6 +123,28 @@ pub struct NewArgs {
         help = "Optional path override for the output markdown file"
     )]
     pub file_override: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "STRATEGY",
+        num_args = 0..=1,
+        default_missing_value = "number",
+        help = "Append a suffix when the link is already taken instead of failing"
+    )]
+    pub disambiguate: Option<Disambiguation>,
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Create the document even when existing titles look like duplicates"
+    )]
+    pub allow_duplicate: bool,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Disambiguation {
+    /// Append `-2`, `-3`, … until the link is free
+    Number,
+    /// Append the first eight hex digits of the document uuid
+    Uuid,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -144,6 +205,178 @@ pub struct ManifestArgs {
         help = "Write manifest to a custom location instead of default"
     )]
     pub output: Option<PathBuf>,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ManifestFormat::Md,
+        help = "Output format; non-Markdown formats default to .claude/manifest.<ext>"
+    )]
+    pub format: ManifestFormat,
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Compare with the manifest on disk, print a diff and exit non-zero on drift"
+    )]
+    pub check: bool,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ManifestGrouping::None,
+        help = "Split the Markdown manifest into one section per type, tag or month"
+    )]
+    pub group_by: ManifestGrouping,
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Start the Markdown manifest with a header block of counts"
+    )]
+    pub header: bool,
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Also write an index.md listing each type's documents into its directory"
+    )]
+    pub type_index: bool,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Template for the Markdown manifest; defaults to .claude/templates/manifest.md if present"
+    )]
+    pub template: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "TZ",
+        help = "IANA timezone for displayed dates; overrides display_timezone in config.yml"
+    )]
+    pub timezone: Option<Tz>,
+    #[arg(
+        short = 'd',
+        long,
+        value_name = "PATH",
+        help = "Workspace directory containing the .claude hierarchy"
+    )]
+    pub directory: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    /// Markdown table
+    Md,
+    Json,
+    Yaml,
+    Csv,
+    Html,
+}
+
+impl ManifestFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ManifestFormat::Md => "md",
+            ManifestFormat::Json => "json",
+            ManifestFormat::Yaml => "yaml",
+            ManifestFormat::Csv => "csv",
+            ManifestFormat::Html => "html",
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestGrouping {
+    /// One flat table
+    None,
+    /// One section per document type
+    Type,
+    /// One section per tag; untagged documents come last
+    Tag,
+    /// One section per month of `updated_at`, newest first
+    Month,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct MigrateArgs {
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        help = "Workspace directory to migrate; defaults to current"
+    )]
+    pub directory: Option<PathBuf>,
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Report pending migrations without rewriting documents"
+    )]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct FmtArgs {
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        help = "Workspace directory to format; defaults to current"
+    )]
+    pub directory: Option<PathBuf>,
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Report unformatted documents and exit non-zero instead of rewriting"
+    )]
+    pub check: bool,
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Insert or refresh a generated table of contents in every document"
+    )]
+    pub toc: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct GraphArgs {
+    #[arg(
+        short,
+        long,
+        value_enum,
+        default_value_t = GraphFormat::Dot,
+        help = "Output format for the relation graph"
+    )]
+    pub format: GraphFormat,
+    #[arg(
+        short = 't',
+        long = "type",
+        value_name = "TYPE",
+        help = "Only include documents of this type"
+    )]
+    pub doc_type: Option<String>,
+    #[arg(
+        short = 'g',
+        long,
+        value_name = "TAG",
+        help = "Only include documents carrying this tag"
+    )]
+    pub tag: Option<String>,
+    #[arg(
+        long,
+        value_name = "LINK",
+        help = "Only include the neighborhood of this document"
+    )]
+    pub around: Option<String>,
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 1,
+        requires = "around",
+        help = "Number of hops to include around --around"
+    )]
+    pub depth: usize,
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        help = "Write the graph to a file instead of stdout"
+    )]
+    pub output: Option<PathBuf>,
     #[arg(
         short = 'd',
         long,
@@ -153,6 +386,221 @@ pub struct ManifestArgs {
     pub directory: Option<PathBuf>,
 }
 
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+    Json,
+    Graphml,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct RelatedArgs {
+    #[arg(value_name = "LINK", help = "Link slug of the document to start from")]
+    pub link: String,
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 2,
+        help = "Maximum number of hops over relations and backlinks"
+    )]
+    pub depth: usize,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct PathArgs {
+    #[arg(value_name = "SOURCE", help = "Link slug where the path starts")]
+    pub source: String,
+    #[arg(value_name = "TARGET", help = "Link slug where the path ends")]
+    pub target: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct BacklinksArgs {
+    #[arg(
+        value_name = "LINK",
+        required_unless_present = "sync",
+        help = "Link slug of the document whose referrers to list"
+    )]
+    pub link: Option<String>,
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        conflicts_with = "link",
+        help = "Regenerate the \"Referenced by\" section at the end of every document"
+    )]
+    pub sync: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct OrphansArgs {
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        help = "Workspace directory containing the .claude hierarchy"
+    )]
+    pub directory: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct StatsArgs {
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        help = "Workspace directory containing the .claude hierarchy"
+    )]
+    pub directory: Option<PathBuf>,
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Include degree distribution, connected components and hubs"
+    )]
+    pub graph: bool,
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 5,
+        help = "Number of hubs to list with --graph"
+    )]
+    pub top: usize,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct MvArgs {
+    #[arg(value_name = "LINK", help = "Link slug of the document to move")]
+    pub link: String,
+    #[arg(
+        value_name = "DESTINATION",
+        help = "Target document type, or a new .md path to rename the document"
+    )]
+    pub destination: String,
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Move even when another document already uses the new link"
+    )]
+    pub force: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct TocArgs {
+    #[arg(
+        value_name = "LINK",
+        help = "Link slug of the document to add a table of contents to"
+    )]
+    pub link: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SuggestLinksArgs {
+    #[arg(
+        value_name = "LINK",
+        help = "Only suggest relations for this document; defaults to every document"
+    )]
+    pub link: Option<String>,
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 3,
+        help = "Maximum number of suggestions per document"
+    )]
+    pub top: usize,
+    #[arg(
+        long,
+        value_name = "SCORE",
+        default_value_t = 0.2,
+        help = "Minimum similarity score (0.0-1.0) for a suggestion"
+    )]
+    pub min_score: f64,
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Write the suggested relations in both directions"
+    )]
+    pub apply: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct DupesArgs {
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        help = "Workspace directory containing the .claude hierarchy"
+    )]
+    pub directory: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "SCORE",
+        default_value_t = DUPLICATE_THRESHOLD,
+        help = "Minimum title similarity (0.0-1.0) to report a pair"
+    )]
+    pub threshold: f64,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ExportArgs {
+    #[command(subcommand)]
+    pub command: ExportCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ExportCommand {
+    /// Render the knowledge base as a static HTML site that works offline
+    Site(SiteArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SiteArgs {
+    #[arg(value_name = "DIR", help = "Directory to write the site into")]
+    pub output: PathBuf,
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        help = "Workspace directory containing the .claude hierarchy"
+    )]
+    pub directory: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ServeArgs {
+    #[arg(
+        short,
+        long,
+        default_value_t = 8080,
+        help = "Port to listen on (0 picks a free one)"
+    )]
+    pub port: u16,
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        default_value = "127.0.0.1",
+        help = "Interface to bind; the default only accepts local connections"
+    )]
+    pub host: String,
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        help = "Workspace directory containing the .claude hierarchy"
+    )]
+    pub directory: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct McpArgs {
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        help = "Workspace directory containing the .claude hierarchy"
+    )]
+    pub directory: Option<PathBuf>,
+}
+
 pub fn run() -> Result<()> {
     let cli = Cli::parse();
     execute(cli)
@@ -166,5 +614,20 @@ pub fn execute(cli: Cli) -> Result<()> {
         Command::Link(args) => link::run(args),
         Command::Validate(args) => validate::run(args),
         Command::Manifest(args) => manifest::run(args),
+        Command::Migrate(args) => migrate::run(args),
+        Command::Fmt(args) => fmt::run(args),
+        Command::Graph(args) => graph::run(args),
+        Command::Related(args) => related::run(args),
+        Command::Path(args) => path::run(args),
+        Command::Backlinks(args) => backlinks::run(args),
+        Command::Orphans(args) => orphans::run(args),
+        Command::Stats(args) => stats::run(args),
+        Command::Mv(args) => mv::run(args),
+        Command::Toc(args) => toc::run(args),
+        Command::SuggestLinks(args) => suggest_links::run(args),
+        Command::Dupes(args) => dupes::run(args),
+        Command::Export(args) => export::run(args),
+        Command::Serve(args) => serve::run(args),
+        Command::Mcp(args) => mcp::run(args),
     }
 }
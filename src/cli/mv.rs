@@ -0,0 +1,238 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use super::validate::type_directory_name;
+use super::MvArgs;
+use crate::config::KbConfig;
+use crate::fs::{
+    display_relative, normalize_path, relative_link, resolve_claude_root_from_cwd,
+    resolve_relative_link, walk_kb_documents, ClaudePaths, DocumentEntry,
+};
+use crate::markdown::{apply_edits, markdown_links, percent_encode_path, wikilinks};
+use crate::model::{normalize_relation_target, slugify_with, Document};
+
+pub fn run(args: MvArgs) -> Result<()> {
+    let (cwd, claude_root) = resolve_claude_root_from_cwd()?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            cwd.display()
+        );
+    }
+
+    let workspace = claude_root
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| cwd.clone());
+    let layout = ClaudePaths::new(claude_root.clone());
+    let entries = walk_kb_documents(&claude_root).collect::<Result<Vec<_>>>()?;
+
+    let mut sources = entries
+        .iter()
+        .filter(|entry| entry.document.front_matter.link == args.link);
+    let source = match (sources.next(), sources.next()) {
+        (Some(source), None) => source,
+        (None, _) => bail!("No document found with link `{}`.", args.link),
+        (Some(_), Some(_)) => bail!(
+            "Multiple documents found with link `{}`; please disambiguate.",
+            args.link
+        ),
+    };
+
+    let config = KbConfig::load(&claude_root)?;
+    let destination = resolve_destination(&cwd, &layout, source, &args.destination)?;
+    let old_link = source.document.front_matter.link.clone();
+    let new_link = slugify_with(
+        destination
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default(),
+        config.slug_policy,
+    );
+    let destination = destination.with_file_name(format!("{new_link}.md"));
+    if destination == source.path {
+        bail!("Source and destination are the same file.");
+    }
+    if destination.exists() {
+        bail!("A document already exists at {}.", destination.display());
+    }
+    if !args.force && new_link != old_link {
+        if let Some(other) = entries
+            .iter()
+            .find(|entry| entry.path != source.path && entry.document.front_matter.link == new_link)
+        {
+            bail!(
+                "Link `{new_link}` is already used by {}; pass --force to move anyway.",
+                display_relative(&workspace, &other.path)
+            );
+        }
+    }
+    let new_type = type_directory_name(&destination, &normalize_path(&claude_root))
+        .filter(|name| layout.is_supported_type(name));
+
+    let relocation = Relocation {
+        from: &source.path,
+        to: &destination,
+    };
+
+    let mut moved = source.document.clone();
+    moved.body = rewrite_links(&moved.body, &source.path, &destination, &relocation);
+    moved.front_matter.link = new_link.clone();
+    if let Some(doc_type) = new_type {
+        moved.front_matter.doc_type = doc_type;
+    }
+    moved.front_matter.touch_updated();
+
+    let mut updated = Vec::new();
+    for entry in entries.iter().filter(|entry| entry.path != source.path) {
+        let mut document = entry.document.clone();
+        document.body = rewrite_links(&document.body, &entry.path, &entry.path, &relocation);
+        if old_link != new_link {
+            rename_references(&mut document, &old_link, &new_link);
+        }
+        if document != entry.document {
+            updated.push((entry.path.clone(), document));
+        }
+    }
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Unable to create {}", parent.display()))?;
+    }
+    fs::write(&destination, moved.to_markdown()?)
+        .with_context(|| format!("Unable to write {}", destination.display()))?;
+    fs::remove_file(&source.path)
+        .with_context(|| format!("Unable to remove {}", source.path.display()))?;
+
+    println!(
+        "Moved {} → {}",
+        display_relative(&workspace, &source.path),
+        display_relative(&workspace, &destination)
+    );
+
+    for (path, document) in updated {
+        fs::write(&path, document.to_markdown()?)
+            .with_context(|| format!("Unable to write {}", path.display()))?;
+        println!(
+            "Updated references in {}",
+            display_relative(&workspace, &path)
+        );
+    }
+
+    Ok(())
+}
+
+struct Relocation<'a> {
+    from: &'a Path,
+    to: &'a Path,
+}
+
+fn resolve_destination(
+    cwd: &Path,
+    layout: &ClaudePaths,
+    source: &DocumentEntry,
+    destination: &str,
+) -> Result<PathBuf> {
+    if layout.is_supported_type(destination) {
+        let file_name = source
+            .path
+            .file_name()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(format!("{}.md", source.document.front_matter.link)));
+        return Ok(layout.type_directory(destination).join(file_name));
+    }
+
+    let path = Path::new(destination);
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        cwd.join(path)
+    };
+    if path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_none_or(|ext| !ext.eq_ignore_ascii_case("md"))
+    {
+        bail!(
+            "Destination `{destination}` must be a document type ({}) or a .md path",
+            layout.known_types().join(", ")
+        );
+    }
+
+    let path = normalize_path(&path);
+    let inside_type_directory = path
+        .strip_prefix(normalize_path(layout.root()))
+        .ok()
+        .and_then(|relative| {
+            let mut components = relative.components();
+            let doc_type = components.next()?.as_os_str().to_str()?;
+            components.next()?;
+            Some(layout.is_supported_type(doc_type))
+        })
+        .unwrap_or(false);
+    if !inside_type_directory {
+        bail!(
+            "Destination `{destination}` must be inside a document type directory under {}",
+            display_relative(cwd, layout.root())
+        );
+    }
+    Ok(path)
+}
+
+fn rewrite_links(body: &str, old_path: &Path, new_path: &Path, relocation: &Relocation) -> String {
+    let mut edits = Vec::new();
+
+    for link in markdown_links(body) {
+        let path = link.path();
+        if link.is_external() || path.is_empty() || path.starts_with('/') {
+            continue;
+        }
+
+        let mut target = resolve_relative_link(old_path, &path);
+        if target == relocation.from {
+            target = relocation.to.to_path_buf();
+        }
+
+        if resolve_relative_link(new_path, &path) == target {
+            continue;
+        }
+
+        let rebased = relative_link(new_path, &target);
+        let suffix_start = link
+            .destination
+            .find(['#', '?'])
+            .unwrap_or(link.destination.len());
+        let suffix = &link.destination[suffix_start..];
+        edits.push((
+            link.span,
+            format!("{}{suffix}", percent_encode_path(&rebased)),
+        ));
+    }
+
+    apply_edits(body, edits)
+}
+
+fn rename_references(document: &mut Document, old_link: &str, new_link: &str) {
+    for relation in &mut document.front_matter.ontological_relations {
+        if normalize_relation_target(&relation.relates_to) == old_link {
+            relation.relates_to = new_link.to_string();
+        }
+    }
+
+    let edits = wikilinks(&document.body)
+        .into_iter()
+        .filter(|wikilink| wikilink.target == old_link)
+        .filter_map(|wikilink| {
+            let inner = &document.body[wikilink.span.clone()];
+            let leading = inner.len() - inner.trim_start().len();
+            inner[leading..].starts_with(old_link).then(|| {
+                let start = wikilink.span.start + leading;
+                (start..start + old_link.len(), new_link.to_string())
+            })
+        })
+        .collect();
+    document.body = apply_edits(&document.body, edits);
+}
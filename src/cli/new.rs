@@ -1,12 +1,21 @@
+use std::collections::HashSet;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, bail, Context, Result};
 
-use super::NewArgs;
-use crate::fs::{display_relative, resolve_claude_root_from_cwd, ClaudePaths};
-use crate::model::{Document, DocumentFrontMatter, OntologicalRelation};
+use super::backlinks::sync_sections;
+use super::{Disambiguation, NewArgs};
+use crate::config::KbConfig;
+use crate::fs::{
+    display_relative, resolve_claude_root_from_cwd, walk_kb_documents, ClaudePaths, DocumentEntry,
+};
+use crate::model::{
+    normalize_relation_target, slugify_with, Document, DocumentFrontMatter, OntologicalRelation,
+    SlugPolicy,
+};
+use crate::similarity::{title_similarity, DUPLICATE_THRESHOLD};
 
 pub fn run(args: NewArgs) -> Result<()> {
     let (cwd, claude_root) = resolve_claude_root_from_cwd()?;
@@ -24,41 +33,207 @@ pub fn run(args: NewArgs) -> Result<()> {
         );
     }
 
+    let config = KbConfig::load(&claude_root)?;
     let doc_type = determine_type(&layout, args.doc_type.as_deref())?;
-    let tags = collect_tags(&args)?;
-    let relations = collect_relations(&args)?;
+    let plan = plan_new_document(
+        &claude_root,
+        &config,
+        DocumentFrontMatter::new(&args.title, doc_type),
+        &NewDocumentOptions {
+            file_override: args.file_override.as_ref().map(|path| cwd.join(path)),
+            disambiguate: args.disambiguate,
+            allow_duplicate: args.allow_duplicate,
+        },
+        |duplicates| confirm_duplicate(&workspace, &args.title, duplicates),
+    )?;
+    if let Some(base) = &plan.replaced_link {
+        println!(
+            "Link `{base}` is taken; using `{}` instead.",
+            plan.front_matter.link
+        );
+    }
+    if plan.link_taken {
+        println!(
+            "Warning: link `{}` is already used by another document; pass --disambiguate to keep links unique.",
+            plan.front_matter.link
+        );
+    }
+
+    let NewDocumentPlan {
+        mut front_matter,
+        output_path,
+        ..
+    } = plan;
+    front_matter.tags = collect_tags(&args)?;
+    front_matter.summary = args.summary.clone();
+    front_matter.ontological_relations = collect_relations(&args)?;
     let body = collect_body()?;
 
-    let mut front_matter = DocumentFrontMatter::new(&args.title, doc_type);
-    front_matter.tags = tags;
-    front_matter.ontological_relations = relations;
-    front_matter.ensure_link_matches_slug();
+    let document = Document::new(front_matter, body);
+    write_new_document(&claude_root, &config, &output_path, &document)?;
 
-    let output_path = compute_output_path(
-        &cwd,
-        &layout,
-        &mut front_matter,
-        args.file_override.as_ref(),
-    )?;
-    ensure_parent_dirs(&output_path)?;
+    println!("Created {}", display_relative(&workspace, &output_path));
+
+    Ok(())
+}
+
+pub(crate) struct NewDocumentOptions {
+    /// Explicit output path, already resolved against the working directory.
+    pub file_override: Option<PathBuf>,
+    pub disambiguate: Option<Disambiguation>,
+    pub allow_duplicate: bool,
+}
+
+pub(crate) struct NewDocumentPlan {
+    pub front_matter: DocumentFrontMatter,
+    pub output_path: PathBuf,
+    pub replaced_link: Option<String>,
+    pub link_taken: bool,
+}
+
+/// Derives the link and output path for `front_matter`. Likely duplicates are handed to
+/// `confirm_duplicates` unless duplicates are allowed or a disambiguation strategy was
+/// chosen; it returns an error to stop.
+pub(crate) fn plan_new_document(
+    claude_root: &Path,
+    config: &KbConfig,
+    mut front_matter: DocumentFrontMatter,
+    options: &NewDocumentOptions,
+    confirm_duplicates: impl FnOnce(&[(f64, DocumentEntry)]) -> Result<()>,
+) -> Result<NewDocumentPlan> {
+    let layout = ClaudePaths::new(claude_root);
+    front_matter.ensure_link_matches_slug(config.slug_policy);
+    let entries: Vec<DocumentEntry> = walk_kb_documents(claude_root)
+        .filter_map(Result::ok)
+        .collect();
+    let existing_links: HashSet<String> = entries
+        .iter()
+        .map(|entry| entry.document.front_matter.link.clone())
+        .collect();
+
+    if !options.allow_duplicate && options.disambiguate.is_none() {
+        let duplicates = similar_documents(entries, &front_matter.title, config.slug_policy);
+        if !duplicates.is_empty() {
+            confirm_duplicates(&duplicates)?;
+        }
+    }
+
+    let base = front_matter.link.clone();
+    let output_path = match (&options.file_override, options.disambiguate) {
+        (Some(path), _) => {
+            ensure_markdown_extension(path)?;
+            if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                front_matter.link = slugify_with(stem, config.slug_policy);
+            }
+            path.clone()
+        }
+        (None, Some(strategy)) => {
+            disambiguate(&layout, &mut front_matter, &existing_links, strategy)?
+        }
+        (None, None) => layout
+            .type_directory(&front_matter.doc_type)
+            .join(format!("{}.md", front_matter.link)),
+    };
+    let replaced_link =
+        (options.file_override.is_none() && front_matter.link != base).then_some(base);
+    let link_taken = options.disambiguate.is_none()
+        && existing_links.contains(&front_matter.link)
+        && !output_path.exists();
+
+    Ok(NewDocumentPlan {
+        front_matter,
+        output_path,
+        replaced_link,
+        link_taken,
+    })
+}
+
+pub(crate) fn describe_duplicates(
+    workspace: &Path,
+    title: &str,
+    duplicates: &[(f64, DocumentEntry)],
+) -> Vec<String> {
+    let mut lines = vec![format!("Possible duplicates of \"{title}\":")];
+    lines.extend(duplicates.iter().map(|(score, entry)| {
+        format!(
+            "  - {} — {} (similarity: {score:.2})",
+            display_relative(workspace, &entry.path),
+            entry.document.front_matter.title
+        )
+    }));
+    lines
+}
+
+pub(crate) fn write_new_document(
+    claude_root: &Path,
+    config: &KbConfig,
+    output_path: &Path,
+    document: &Document,
+) -> Result<()> {
+    ensure_parent_dirs(output_path)?;
 
     if output_path.exists() {
         bail!(
-            "A document already exists at {}; choose a different title or override path",
+            "A document already exists at {}; choose a different title, override path, or pass --disambiguate",
             output_path.display()
         );
     }
 
-    let document = Document::new(front_matter, body);
     let content = document.to_markdown()?;
-    fs::write(&output_path, content)
+    fs::write(output_path, content)
         .with_context(|| format!("Unable to write {}", output_path.display()))?;
 
-    println!("Created {}", display_relative(&workspace, &output_path));
+    if config.backlinks_section {
+        sync_sections(claude_root)?;
+    }
 
     Ok(())
 }
 
+fn similar_documents(
+    entries: Vec<DocumentEntry>,
+    title: &str,
+    slug_policy: SlugPolicy,
+) -> Vec<(f64, DocumentEntry)> {
+    let link = slugify_with(title, slug_policy);
+    let mut similar: Vec<(f64, DocumentEntry)> = entries
+        .into_iter()
+        .map(|entry| {
+            let front = &entry.document.front_matter;
+            let score = title_similarity(title, &link, &front.title, &front.link);
+            (score, entry)
+        })
+        .filter(|(score, _)| *score >= DUPLICATE_THRESHOLD)
+        .collect();
+    similar.sort_by(|left, right| right.0.total_cmp(&left.0));
+    similar
+}
+
+fn confirm_duplicate(
+    workspace: &Path,
+    title: &str,
+    duplicates: &[(f64, DocumentEntry)],
+) -> Result<()> {
+    for line in describe_duplicates(workspace, title, duplicates) {
+        println!("{line}");
+    }
+
+    if !io::stdin().is_terminal() {
+        println!("Warning: creating it anyway; update the existing document instead if it covers the same topic.");
+        return Ok(());
+    }
+
+    print!("Create anyway? [y/N]: ");
+    io::stdout().flush().ok();
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    if matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+        Ok(())
+    } else {
+        bail!("Aborted; update the existing document or pass --allow-duplicate")
+    }
+}
+
 fn determine_type(layout: &ClaudePaths, provided: Option<&str>) -> Result<String> {
     if let Some(doc_type) = provided {
         validate_type(layout, doc_type)?;
@@ -91,7 +266,7 @@ fn determine_type(layout: &ClaudePaths, provided: Option<&str>) -> Result<String
     }
 }
 
-fn validate_type(layout: &ClaudePaths, doc_type: &str) -> Result<()> {
+pub(crate) fn validate_type(layout: &ClaudePaths, doc_type: &str) -> Result<()> {
     if layout.is_supported_type(doc_type) {
         Ok(())
     } else {
@@ -102,22 +277,26 @@ fn validate_type(layout: &ClaudePaths, doc_type: &str) -> Result<()> {
     }
 }
 
+pub(crate) fn normalize_tags<'a>(tags: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.into_iter().map(str::trim) {
+        if !tag.is_empty() && !normalized.iter().any(|existing| existing == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
 fn collect_tags(args: &NewArgs) -> Result<Vec<String>> {
     if !args.tags.is_empty() {
-        return Ok(args.tags.iter().map(|t| t.trim().to_string()).collect());
+        return Ok(normalize_tags(args.tags.iter().map(String::as_str)));
     }
 
     print!("Tags (comma separated, optional): ");
     io::stdout().flush().ok();
     let mut input = String::new();
     io::stdin().read_line(&mut input)?;
-    let tags = input
-        .split(',')
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .map(ToString::to_string)
-        .collect();
-    Ok(tags)
+    Ok(normalize_tags(input.split(',')))
 }
 
 fn collect_relations(args: &NewArgs) -> Result<Vec<OntologicalRelation>> {
@@ -126,7 +305,7 @@ fn collect_relations(args: &NewArgs) -> Result<Vec<OntologicalRelation>> {
             .relates_to
             .iter()
             .map(|link| OntologicalRelation {
-                relates_to: link.trim().to_string(),
+                relates_to: normalize_relation_target(link),
             })
             .collect());
     }
@@ -140,7 +319,7 @@ fn collect_relations(args: &NewArgs) -> Result<Vec<OntologicalRelation>> {
         .map(str::trim)
         .filter(|value| !value.is_empty())
         .map(|value| OntologicalRelation {
-            relates_to: value.to_string(),
+            relates_to: normalize_relation_target(value),
         })
         .collect();
     Ok(relations)
@@ -161,29 +340,35 @@ fn collect_body() -> Result<String> {
     Ok(lines.join("\n"))
 }
 
-fn compute_output_path(
-    cwd: &Path,
+fn disambiguate(
     layout: &ClaudePaths,
     front_matter: &mut DocumentFrontMatter,
-    override_path: Option<&PathBuf>,
+    existing_links: &HashSet<String>,
+    strategy: Disambiguation,
 ) -> Result<PathBuf> {
-    if let Some(custom) = override_path {
-        let path = if custom.is_relative() {
-            cwd.join(custom)
-        } else {
-            custom.clone()
-        };
-        ensure_markdown_extension(&path)?;
-        if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
-            let slug = crate::model::slugify(stem);
-            front_matter.link = slug;
-        }
-        return Ok(path);
+    let type_dir = layout.type_directory(&front_matter.doc_type);
+    let path_for = |link: &str| type_dir.join(format!("{link}.md"));
+    let is_taken = |link: &str| existing_links.contains(link) || path_for(link).exists();
+
+    let base = front_matter.link.clone();
+    if !is_taken(&base) {
+        return Ok(path_for(&base));
+    }
+
+    let candidate = match strategy {
+        Disambiguation::Number => (2..)
+            .map(|number| format!("{base}-{number}"))
+            .find(|link| !is_taken(link))
+            .unwrap_or_default(),
+        Disambiguation::Uuid => format!("{base}-{}", front_matter.short_uuid()),
+    };
+
+    if is_taken(&candidate) {
+        bail!("Unable to find a free link for `{base}`; choose a different title");
     }
 
-    let mut path = layout.type_directory(&front_matter.doc_type);
-    path.push(format!("{}.md", front_matter.link));
-    Ok(path)
+    front_matter.link = candidate;
+    Ok(path_for(&front_matter.link))
 }
 
 fn ensure_markdown_extension(path: &Path) -> Result<()> {
@@ -0,0 +1,37 @@
+use anyhow::{bail, Result};
+
+use super::OrphansArgs;
+use crate::fs::{display_relative, resolve_claude_root};
+use crate::graph::KbGraph;
+
+pub fn run(args: OrphansArgs) -> Result<()> {
+    let (base_dir, claude_root) = resolve_claude_root(args.directory.as_deref())?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            base_dir.display()
+        );
+    }
+
+    let workspace = claude_root.parent().unwrap_or(&claude_root);
+    let graph = KbGraph::load(&claude_root)?;
+    let orphans: Vec<_> = graph.orphans().collect();
+
+    if orphans.is_empty() {
+        println!("No orphaned documents found.");
+        return Ok(());
+    }
+
+    for (index, node) in orphans.iter().enumerate() {
+        println!(
+            "{}. {} — {} (type: {})",
+            index + 1,
+            display_relative(workspace, &node.path),
+            node.title,
+            node.doc_type
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anyhow::{bail, Result};
+
+use super::PathArgs;
+use crate::fs::resolve_claude_root_from_cwd;
+use crate::graph::KbGraph;
+
+pub fn run(args: PathArgs) -> Result<()> {
+    let (cwd, claude_root) = resolve_claude_root_from_cwd()?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            cwd.display()
+        );
+    }
+
+    let graph = KbGraph::load(&claude_root)?;
+    for link in [&args.source, &args.target] {
+        if !graph.contains(link) {
+            bail!("No document found with link `{link}`.");
+        }
+    }
+
+    let Some(path) = graph.shortest_path(&args.source, &args.target) else {
+        println!(
+            "No relation path connects `{}` and `{}`.",
+            args.source, args.target
+        );
+        return Ok(());
+    };
+
+    let mut rendered = path[0].clone();
+    for pair in path.windows(2) {
+        let arrow = if graph.has_edge(&pair[0], &pair[1]) {
+            "→"
+        } else {
+            "←"
+        };
+        rendered.push_str(&format!(" {arrow} {}", pair[1]));
+    }
+
+    println!("{rendered}");
+    println!("{} hop(s)", path.len() - 1);
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anyhow::{bail, Result};
+
+use super::RelatedArgs;
+use crate::fs::{display_relative, resolve_claude_root_from_cwd};
+use crate::graph::KbGraph;
+
+pub fn run(args: RelatedArgs) -> Result<()> {
+    let (cwd, claude_root) = resolve_claude_root_from_cwd()?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            cwd.display()
+        );
+    }
+
+    let workspace = claude_root.parent().unwrap_or(&claude_root);
+    let graph = KbGraph::load(&claude_root)?;
+
+    if !graph.contains(&args.link) {
+        bail!("No document found with link `{}`.", args.link);
+    }
+
+    let mut related: Vec<(usize, String)> = graph
+        .distances_from(&args.link, args.depth)
+        .into_iter()
+        .filter(|(_, distance)| *distance > 0)
+        .map(|(link, distance)| (distance, link))
+        .collect();
+    related.sort();
+
+    if related.is_empty() {
+        println!(
+            "No documents within {} hop(s) of `{}`.",
+            args.depth, args.link
+        );
+        return Ok(());
+    }
+
+    for (index, (distance, link)) in related.iter().enumerate() {
+        let Some(node) = graph.node(link) else {
+            continue;
+        };
+        println!(
+            "{}. {} — {} (type: {}, hops: {})",
+            index + 1,
+            display_relative(workspace, &node.path),
+            node.title,
+            node.doc_type,
+            distance
+        );
+    }
+
+    Ok(())
+}
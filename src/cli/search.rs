@@ -1,9 +1,13 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Result};
+use serde::Serialize;
 
 use super::SearchArgs;
-use crate::fs::{display_relative, resolve_claude_root_from_cwd, walk_kb_documents};
+use crate::config::KbConfig;
+use crate::fs::{display_relative, resolve_claude_root_from_cwd, walk_kb_documents, DocumentEntry};
+use crate::markdown::{strip_marked_section, wikilinks, BACKLINKS_SECTION};
+use crate::model::{slugify_with, SlugPolicy};
 
 pub fn run(args: SearchArgs) -> Result<()> {
     let (cwd, claude_root) = resolve_claude_root_from_cwd()?;
@@ -15,16 +19,15 @@ pub fn run(args: SearchArgs) -> Result<()> {
         );
     }
 
-    let terms: Vec<String> = args.terms.iter().map(|term| term.to_lowercase()).collect();
-    let tag_filters: Vec<String> = args.tags.iter().map(|tag| tag.to_lowercase()).collect();
-
+    let config = KbConfig::load(&claude_root)?;
     let documents = collect_documents(&claude_root)?;
-    let mut matches: Vec<SearchMatch> = documents
-        .into_iter()
-        .filter_map(|entry| filter_match(&claude_root, &entry, &terms, &tag_filters))
-        .collect();
-
-    matches.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+    let matches = search(
+        &claude_root,
+        &documents,
+        &args.terms,
+        &args.tags,
+        config.slug_policy,
+    );
 
     if matches.is_empty() {
         println!("No matching entries found.");
@@ -49,24 +52,46 @@ pub fn run(args: SearchArgs) -> Result<()> {
     Ok(())
 }
 
-#[derive(Debug)]
-struct SearchMatch {
-    title: String,
-    doc_type: String,
-    tags: Vec<String>,
-    path: PathBuf,
-    score: usize,
+#[derive(Debug, Serialize)]
+pub(crate) struct SearchMatch {
+    pub(crate) title: String,
+    pub(crate) link: String,
+    #[serde(rename = "type")]
+    pub(crate) doc_type: String,
+    pub(crate) tags: Vec<String>,
+    pub(crate) path: PathBuf,
+    pub(crate) score: usize,
+}
+
+pub(crate) fn search(
+    claude_root: &Path,
+    documents: &[DocumentEntry],
+    terms: &[String],
+    tags: &[String],
+    slug_policy: SlugPolicy,
+) -> Vec<SearchMatch> {
+    let terms: Vec<String> = terms.iter().map(|term| term.to_lowercase()).collect();
+    let tag_filters: Vec<String> = tags.iter().map(|tag| tag.to_lowercase()).collect();
+
+    let mut matches: Vec<SearchMatch> = documents
+        .iter()
+        .filter_map(|entry| filter_match(claude_root, entry, &terms, &tag_filters, slug_policy))
+        .collect();
+
+    matches.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+    matches
 }
 
-fn collect_documents(claude_root: &Path) -> Result<Vec<crate::fs::DocumentEntry>> {
+fn collect_documents(claude_root: &Path) -> Result<Vec<DocumentEntry>> {
     walk_kb_documents(claude_root).collect()
 }
 
 fn filter_match(
     claude_root: &Path,
-    entry: &crate::fs::DocumentEntry,
+    entry: &DocumentEntry,
     terms: &[String],
     tag_filters: &[String],
+    slug_policy: SlugPolicy,
 ) -> Option<SearchMatch> {
     let front = &entry.document.front_matter;
 
@@ -80,7 +105,8 @@ fn filter_match(
         }
     }
 
-    let searchable = build_search_blob(front, &entry.document.body);
+    let body = strip_marked_section(&entry.document.body, BACKLINKS_SECTION);
+    let searchable = build_search_blob(front, &body, slug_policy);
 
     let mut score = 0;
     for term in terms {
@@ -94,6 +120,7 @@ fn filter_match(
 
     Some(SearchMatch {
         title: front.title.clone(),
+        link: front.link.clone(),
         doc_type: front.doc_type.clone(),
         tags: front.tags.clone(),
         path: PathBuf::from(display_relative(workspace, &entry.path)),
@@ -101,13 +128,20 @@ fn filter_match(
     })
 }
 
-fn build_search_blob(front: &crate::model::DocumentFrontMatter, body: &str) -> String {
+fn build_search_blob(
+    front: &crate::model::DocumentFrontMatter,
+    body: &str,
+    slug_policy: SlugPolicy,
+) -> String {
     let mut blob = vec![
         front.title.to_lowercase(),
         front.link.to_lowercase(),
         front.doc_type.to_lowercase(),
         body.to_lowercase(),
     ];
+    if let Some(summary) = &front.summary {
+        blob.push(summary.to_lowercase());
+    }
     if !front.tags.is_empty() {
         blob.push(front.tags.join(" ").to_lowercase());
     }
@@ -120,5 +154,13 @@ fn build_search_blob(front: &crate::model::DocumentFrontMatter, body: &str) -> S
             .join(" ");
         blob.push(relations);
     }
+    let implicit = wikilinks(body)
+        .iter()
+        .filter(|wikilink| !wikilink.target.is_empty())
+        .map(|wikilink| slugify_with(&wikilink.target, slug_policy))
+        .collect::<Vec<_>>();
+    if !implicit.is_empty() {
+        blob.push(implicit.join(" "));
+    }
     blob.join(" ")
 }
@@ -0,0 +1,306 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono_tz::Tz;
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use super::graph::render as render_graph;
+use super::manifest::render_default as render_manifest;
+use super::search::search;
+use super::{GraphFormat, ManifestFormat, ServeArgs};
+use crate::config::KbConfig;
+use crate::fs::{
+    display_relative, is_ignored_path, resolve_claude_root, walk_kb_documents, DocumentEntry,
+};
+use crate::graph::KbGraph;
+use crate::markdown::percent_decode;
+use crate::model::{iso8601, SlugPolicy};
+use crate::site::Site;
+
+pub fn run(args: ServeArgs) -> Result<()> {
+    let (base_dir, claude_root) = resolve_claude_root(args.directory.as_deref())?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            base_dir.display()
+        );
+    }
+
+    let mut snapshot = Snapshot::load(&claude_root)?;
+
+    let stale = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&stale);
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if event.is_ok_and(|event| {
+            matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            )
+        }) {
+            flag.store(true, Ordering::SeqCst);
+        }
+    })
+    .context("Unable to watch the .claude directory")?;
+    watcher
+        .watch(&claude_root, RecursiveMode::Recursive)
+        .with_context(|| format!("Unable to watch {}", claude_root.display()))?;
+
+    let server = Server::http((args.host.as_str(), args.port))
+        .map_err(|error| anyhow!("Unable to listen on {}:{}: {error}", args.host, args.port))?;
+    let address = server
+        .server_addr()
+        .to_ip()
+        .map(|address| address.to_string())
+        .unwrap_or_else(|| format!("{}:{}", args.host, args.port));
+    println!(
+        "Serving {} document(s) from {} at http://{address}/ (Ctrl+C to stop)",
+        snapshot.entries.len(),
+        display_relative(&base_dir, &claude_root)
+    );
+
+    for request in server.incoming_requests() {
+        if stale.swap(false, Ordering::SeqCst) {
+            match Snapshot::load(&claude_root) {
+                Ok(fresh) => {
+                    snapshot = fresh;
+                    println!("Reloaded {} document(s)", snapshot.entries.len());
+                }
+                Err(error) => eprintln!("warning: keeping the previous snapshot — {error:#}"),
+            }
+        }
+        handle(request, &claude_root, &snapshot);
+    }
+
+    Ok(())
+}
+
+struct Snapshot {
+    entries: Vec<DocumentEntry>,
+    graph: KbGraph,
+    site: Site,
+    manifest: String,
+    manifest_json: String,
+    slug_policy: SlugPolicy,
+}
+
+impl Snapshot {
+    fn load(claude_root: &Path) -> Result<Self> {
+        let config = KbConfig::load(claude_root)?;
+        let timezone = config.display_timezone.unwrap_or(Tz::UTC);
+        let entries = walk_kb_documents(claude_root).collect::<Result<Vec<_>>>()?;
+        let graph = KbGraph::from_entries(&entries, config.slug_policy);
+        let site = Site::build(claude_root, &entries, timezone, config.slug_policy);
+
+        Ok(Self {
+            manifest: render_manifest(claude_root, ManifestFormat::Md)?,
+            manifest_json: render_manifest(claude_root, ManifestFormat::Json)?,
+            entries,
+            graph,
+            site,
+            slug_policy: config.slug_policy,
+        })
+    }
+}
+
+pub(crate) fn document_payload(
+    claude_root: &Path,
+    graph: &KbGraph,
+    entry: &DocumentEntry,
+) -> Value {
+    let front = &entry.document.front_matter;
+    let workspace = claude_root.parent().unwrap_or(claude_root);
+    json!({
+        "title": front.title,
+        "link": front.link,
+        "type": front.doc_type,
+        "uuid": front.uuid.hyphenated().to_string(),
+        "path": display_relative(workspace, &entry.path),
+        "summary": front.summary,
+        "tags": front.tags,
+        "relations": front
+            .ontological_relations
+            .iter()
+            .map(|relation| relation.relates_to.clone())
+            .collect::<Vec<_>>(),
+        "created_at": iso8601::format(&front.created_at),
+        "updated_at": iso8601::format(&front.last_updated()),
+        "outgoing": graph.outgoing(&front.link).collect::<Vec<_>>(),
+        "incoming": graph.incoming(&front.link).collect::<Vec<_>>(),
+        "body": entry.document.body,
+    })
+}
+
+fn handle(request: Request, claude_root: &Path, snapshot: &Snapshot) {
+    let (status, content_type, body) = if matches!(request.method(), Method::Get | Method::Head) {
+        route(request.url(), claude_root, snapshot)
+    } else {
+        (405, "text/plain", b"Method not allowed\n".to_vec())
+    };
+
+    let mut response = Response::from_data(body).with_status_code(status);
+    if let Ok(header) = Header::from_bytes("Content-Type", content_type) {
+        response = response.with_header(header);
+    }
+    if let Err(error) = request.respond(response) {
+        eprintln!("warning: unable to send response — {error}");
+    }
+}
+
+fn route(url: &str, claude_root: &Path, snapshot: &Snapshot) -> (u16, &'static str, Vec<u8>) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let path = percent_decode(path);
+
+    match path.as_str() {
+        "/api/search" => {
+            let params = query_params(query);
+            let terms: Vec<String> = params
+                .iter()
+                .filter(|(key, _)| key == "q")
+                .flat_map(|(_, value)| value.split_whitespace().map(str::to_string))
+                .collect();
+            let tags: Vec<String> = params
+                .iter()
+                .filter(|(key, _)| key == "tag")
+                .map(|(_, value)| value.clone())
+                .collect();
+            let matches = search(
+                claude_root,
+                &snapshot.entries,
+                &terms,
+                &tags,
+                snapshot.slug_policy,
+            );
+            json_response(200, &json!(matches))
+        }
+        "/api/graph" => {
+            let workspace = claude_root.parent().unwrap_or(claude_root);
+            match render_graph(&snapshot.graph, workspace, GraphFormat::Json) {
+                Ok(graph) => (200, "application/json", graph.into_bytes()),
+                Err(error) => json_response(500, &json!({ "error": format!("{error:#}") })),
+            }
+        }
+        "/manifest.md" => (
+            200,
+            "text/markdown; charset=utf-8",
+            snapshot.manifest.clone().into_bytes(),
+        ),
+        "/manifest.json" => (
+            200,
+            "application/json",
+            snapshot.manifest_json.clone().into_bytes(),
+        ),
+        _ => {
+            if let Some(reference) = path.strip_prefix("/api/doc/") {
+                return document_response(reference, claude_root, snapshot);
+            }
+            static_response(&path, claude_root, snapshot)
+        }
+    }
+}
+
+fn document_response(
+    reference: &str,
+    claude_root: &Path,
+    snapshot: &Snapshot,
+) -> (u16, &'static str, Vec<u8>) {
+    let entry = snapshot.graph.resolve(reference).and_then(|link| {
+        snapshot
+            .entries
+            .iter()
+            .find(|entry| entry.document.front_matter.link == link)
+    });
+    match entry {
+        Some(entry) => json_response(200, &document_payload(claude_root, &snapshot.graph, entry)),
+        None => json_response(
+            404,
+            &json!({ "error": format!("No document found for `{reference}`") }),
+        ),
+    }
+}
+
+/// Rendered site pages first, then raw files (Markdown sources, images) under the type
+/// directories so links from the manifest and the pages resolve.
+fn static_response(
+    path: &str,
+    claude_root: &Path,
+    snapshot: &Snapshot,
+) -> (u16, &'static str, Vec<u8>) {
+    let mut relative = path.trim_start_matches('/').to_string();
+    if relative.is_empty() || relative.ends_with('/') {
+        relative.push_str("index.html");
+    }
+
+    if let Some(page) = snapshot.site.page(&relative) {
+        return (200, content_type(&relative), page.as_bytes().to_vec());
+    }
+
+    if let Some(file) = source_file(claude_root, &relative) {
+        if let Ok(bytes) = fs::read(&file) {
+            return (200, content_type(&relative), bytes);
+        }
+    }
+
+    (
+        404,
+        "text/plain",
+        format!("Not found: {path}\n").into_bytes(),
+    )
+}
+
+fn source_file(claude_root: &Path, relative: &str) -> Option<PathBuf> {
+    let relative = Path::new(relative);
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+    let path = claude_root.join(relative);
+    if relative.components().count() < 2 || is_ignored_path(&path, claude_root) {
+        return None;
+    }
+    // Symlinks may lead anywhere; only serve files that really live under the tree.
+    let resolved = fs::canonicalize(&path).ok()?;
+    let root = fs::canonicalize(claude_root).ok()?;
+    (resolved.starts_with(&root) && resolved.is_file()).then_some(resolved)
+}
+
+fn content_type(path: &str) -> &'static str {
+    match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("css") => "text/css",
+        Some("js") => "text/javascript",
+        Some("json") => "application/json",
+        Some("md") => "text/markdown; charset=utf-8",
+        Some("txt") => "text/plain; charset=utf-8",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("svg") => "image/svg+xml",
+        Some("pdf") => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+fn json_response(status: u16, value: &Value) -> (u16, &'static str, Vec<u8>) {
+    let body = serde_json::to_vec_pretty(value).unwrap_or_default();
+    (status, "application/json", body)
+}
+
+fn query_params(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let decode = |part: &str| percent_decode(&part.replace('+', " "));
+            (decode(key), decode(value))
+        })
+        .collect()
+}
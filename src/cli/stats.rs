@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+
+use super::StatsArgs;
+use crate::config::KbConfig;
+use crate::fs::{resolve_claude_root, walk_kb_documents, CLAUDE_DIRECTORIES};
+use crate::graph::KbGraph;
+
+pub fn run(args: StatsArgs) -> Result<()> {
+    let (base_dir, claude_root) = resolve_claude_root(args.directory.as_deref())?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            base_dir.display()
+        );
+    }
+
+    let entries = walk_kb_documents(&claude_root).collect::<Result<Vec<_>>>()?;
+
+    let mut by_type: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut by_tag: BTreeMap<String, usize> = BTreeMap::new();
+    for entry in &entries {
+        let front = &entry.document.front_matter;
+        *by_type.entry(front.doc_type.as_str()).or_default() += 1;
+        for tag in &front.tags {
+            *by_tag.entry(tag.to_lowercase()).or_default() += 1;
+        }
+    }
+
+    println!("Documents: {}", entries.len());
+    for directory in CLAUDE_DIRECTORIES {
+        println!("  {directory}: {}", by_type.get(directory).unwrap_or(&0));
+    }
+    println!("Distinct tags: {}", by_tag.len());
+
+    if !args.graph {
+        return Ok(());
+    }
+
+    let graph = KbGraph::from_entries(&entries, KbConfig::load(&claude_root)?.slug_policy);
+    let components = graph.connected_components();
+
+    println!();
+    println!("Relations: {}", graph.edges().count());
+    println!("Orphans: {}", graph.orphans().count());
+    println!(
+        "Connected components: {} (largest: {})",
+        components.len(),
+        components.first().map(Vec::len).unwrap_or(0)
+    );
+
+    let mut distribution: BTreeMap<usize, usize> = BTreeMap::new();
+    for node in graph.nodes() {
+        *distribution.entry(graph.degree(&node.link)).or_default() += 1;
+    }
+    println!("Degree distribution:");
+    for (degree, count) in &distribution {
+        println!("  {degree}: {count}");
+    }
+
+    let mut hubs: Vec<(usize, &str, &str)> = graph
+        .nodes()
+        .map(|node| {
+            (
+                graph.degree(&node.link),
+                node.link.as_str(),
+                node.title.as_str(),
+            )
+        })
+        .filter(|(degree, _, _)| *degree > 0)
+        .collect();
+    hubs.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+
+    println!("Top hubs:");
+    if hubs.is_empty() {
+        println!("  —");
+    }
+    for (index, (degree, link, title)) in hubs.iter().take(args.top).enumerate() {
+        println!("  {}. {link} — {title} (degree: {degree})", index + 1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use std::collections::BTreeSet;
+
+use anyhow::{bail, Result};
+
+use super::backlinks::sync_sections;
+use super::link::{insert_relation, write_document};
+use super::SuggestLinksArgs;
+use crate::config::KbConfig;
+use crate::fs::{display_relative, resolve_claude_root_from_cwd, walk_kb_documents, DocumentEntry};
+use crate::graph::KbGraph;
+use crate::markdown::{strip_marked_section, BACKLINKS_SECTION, TOC_SECTION};
+use crate::similarity::TfIdf;
+
+pub fn run(args: SuggestLinksArgs) -> Result<()> {
+    let (cwd, claude_root) = resolve_claude_root_from_cwd()?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            cwd.display()
+        );
+    }
+
+    let workspace = claude_root.parent().unwrap_or(&claude_root);
+    let config = KbConfig::load(&claude_root)?;
+    let mut entries = walk_kb_documents(&claude_root).collect::<Result<Vec<_>>>()?;
+    let graph = KbGraph::from_entries(&entries, config.slug_policy);
+
+    let sources: Vec<usize> = match &args.link {
+        Some(link) => {
+            let found: Vec<usize> = (0..entries.len())
+                .filter(|index| &entries[*index].document.front_matter.link == link)
+                .collect();
+            match found.len() {
+                0 => bail!("No document found with link `{link}`."),
+                1 => found,
+                _ => bail!("Multiple documents found with link `{link}`; please disambiguate."),
+            }
+        }
+        None => (0..entries.len()).collect(),
+    };
+
+    let texts: Vec<String> = entries.iter().map(similarity_text).collect();
+    let index = TfIdf::new(&texts);
+
+    let mut pairs = BTreeSet::new();
+    let mut printed = 0;
+    for source in sources {
+        let link = &entries[source].document.front_matter.link;
+        let neighbors = graph.neighbors(link);
+        let mut candidates: Vec<(f64, usize)> = (0..entries.len())
+            .filter(|candidate| *candidate != source)
+            .filter(|candidate| {
+                let other = &entries[*candidate].document.front_matter.link;
+                other != link && !neighbors.contains(other.as_str())
+            })
+            .map(|candidate| (index.similarity(source, candidate), candidate))
+            .filter(|(score, _)| *score >= args.min_score)
+            .collect();
+        candidates.sort_by(|left, right| {
+            right
+                .0
+                .total_cmp(&left.0)
+                .then_with(|| entries[left.1].path.cmp(&entries[right.1].path))
+        });
+        candidates.truncate(args.top);
+
+        if candidates.is_empty() {
+            continue;
+        }
+
+        let front = &entries[source].document.front_matter;
+        println!("{} — {}", front.link, front.title);
+        for (rank, (score, candidate)) in candidates.iter().enumerate() {
+            let other = &entries[*candidate];
+            println!(
+                "  {}. {} — {} (score: {score:.2})",
+                rank + 1,
+                display_relative(workspace, &other.path),
+                other.document.front_matter.title
+            );
+            pairs.insert((source.min(*candidate), source.max(*candidate)));
+        }
+        printed += 1;
+    }
+
+    if printed == 0 {
+        println!("No relation suggestions found.");
+        return Ok(());
+    }
+
+    if !args.apply {
+        println!("Run with --apply to write these relations.");
+        return Ok(());
+    }
+
+    let mut changed = BTreeSet::new();
+    for (left, right) in &pairs {
+        let left_front = entries[*left].document.front_matter.clone();
+        let right_front = entries[*right].document.front_matter.clone();
+        if insert_relation(&mut entries[*left].document, &right_front, false) {
+            changed.insert(*left);
+        }
+        if insert_relation(&mut entries[*right].document, &left_front, false) {
+            changed.insert(*right);
+        }
+        println!(
+            "Linked {} <-> {}",
+            display_relative(workspace, &entries[*left].path),
+            display_relative(workspace, &entries[*right].path)
+        );
+    }
+
+    for index in changed {
+        write_document(&entries[index])?;
+    }
+
+    if config.backlinks_section {
+        sync_sections(&claude_root)?;
+    }
+
+    Ok(())
+}
+
+fn similarity_text(entry: &DocumentEntry) -> String {
+    let front = &entry.document.front_matter;
+    let body = strip_marked_section(
+        &strip_marked_section(&entry.document.body, BACKLINKS_SECTION),
+        TOC_SECTION,
+    );
+    format!(
+        "{title}\n{title}\n{}\n{body}",
+        front.tags.join(" "),
+        title = front.title
+    )
+}
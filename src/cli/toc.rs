@@ -0,0 +1,61 @@
+use std::fs;
+
+use anyhow::{bail, Context, Result};
+
+use super::TocArgs;
+use crate::fs::{display_relative, resolve_claude_root_from_cwd, walk_kb_documents};
+use crate::markdown::{
+    outline, render_toc, replace_marked_section, strip_marked_section, Placement,
+    BACKLINKS_SECTION, TOC_SECTION,
+};
+
+pub fn run(args: TocArgs) -> Result<()> {
+    let (cwd, claude_root) = resolve_claude_root_from_cwd()?;
+
+    if !claude_root.exists() {
+        bail!(
+            "No .claude directory found under {}. Run `kb-claude init` first.",
+            cwd.display()
+        );
+    }
+
+    let workspace = claude_root.parent().unwrap_or(&claude_root);
+    let mut matches = walk_kb_documents(&claude_root)
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|entry| entry.document.front_matter.link == args.link)
+        .collect::<Vec<_>>();
+
+    let mut entry = match matches.len() {
+        0 => bail!("No document found with link `{}`.", args.link),
+        1 => matches.remove(0),
+        _ => bail!(
+            "Multiple documents found with link `{}`; please disambiguate.",
+            args.link
+        ),
+    };
+
+    let body = with_toc(&entry.document.body);
+    if body.trim_end() == entry.document.body.trim_end() {
+        println!(
+            "Table of contents already up to date in {}",
+            display_relative(workspace, &entry.path)
+        );
+        return Ok(());
+    }
+
+    entry.document.body = body;
+    fs::write(&entry.path, entry.document.to_markdown()?)
+        .with_context(|| format!("Unable to write {}", entry.path.display()))?;
+    println!(
+        "Updated table of contents in {}",
+        display_relative(workspace, &entry.path)
+    );
+    Ok(())
+}
+
+pub(crate) fn with_toc(body: &str) -> String {
+    let content = strip_marked_section(&strip_marked_section(body, TOC_SECTION), BACKLINKS_SECTION);
+    let toc = render_toc(&outline(&content));
+    replace_marked_section(body, TOC_SECTION, toc.as_deref(), Placement::Start)
+}
@@ -1,10 +1,18 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, bail, Result};
+use chrono::Utc;
 
 use super::ValidateArgs;
-use crate::fs::{display_relative, resolve_claude_root, walk_kb_documents, ClaudePaths};
-use crate::model::Document;
+use crate::config::KbConfig;
+use crate::fs::{
+    display_relative, resolve_claude_root, resolve_relative_link, walk_kb_documents, ClaudePaths,
+    DocumentEntry,
+};
+use crate::graph::KbGraph;
+use crate::markdown::{has_anchor, markdown_links, outline, wikilinks, Heading};
+use crate::model::{iso8601, Document, CURRENT_SCHEMA_VERSION};
 
 pub fn run(args: ValidateArgs) -> Result<()> {
     let (target_dir, claude_root) = resolve_claude_root(args.directory.as_deref())?;
@@ -22,7 +30,8 @@ pub fn run(args: ValidateArgs) -> Result<()> {
         .unwrap_or_else(|| target_dir.to_path_buf());
 
     let layout = ClaudePaths::new(claude_root.clone());
-    let findings = collect_findings(&claude_root, &layout)?;
+    let config = KbConfig::load(&claude_root)?;
+    let findings = collect_findings(&claude_root, &layout, &config)?;
 
     if findings.is_empty() {
         println!(
@@ -32,49 +41,64 @@ pub fn run(args: ValidateArgs) -> Result<()> {
         return Ok(());
     }
 
-    print_findings(&findings, &workspace);
-
-    let error_count = findings
-        .iter()
-        .filter(|finding| finding.severity == Severity::Error)
-        .count();
-    let warning_count = findings
-        .iter()
-        .filter(|finding| finding.severity == Severity::Warning)
-        .count();
+    for finding in &findings {
+        println!("{}", format_finding(finding, &workspace));
+    }
 
-    if error_count > 0 || (args.strict && warning_count > 0) {
-        let mut parts = Vec::new();
-        if error_count > 0 {
-            parts.push(format!("{error_count} error(s)"));
-        }
-        if args.strict && warning_count > 0 {
-            parts.push(format!("{warning_count} warning(s)"));
-        }
-        bail!("Validation failed with {}.", parts.join(" and "));
+    if let Some(failure) = failure_summary(&findings, args.strict) {
+        bail!("{failure}");
     }
 
     println!(
-        "Validation completed with {warning_count} warning(s). Run with --strict to fail on warnings."
+        "Validation completed with {} warning(s). Run with --strict to fail on warnings.",
+        count(&findings, Severity::Warning)
     );
     Ok(())
 }
 
+fn count(findings: &[Finding], severity: Severity) -> usize {
+    findings
+        .iter()
+        .filter(|finding| finding.severity == severity)
+        .count()
+}
+
+pub(crate) fn failure_summary(findings: &[Finding], strict: bool) -> Option<String> {
+    let error_count = count(findings, Severity::Error);
+    let warning_count = count(findings, Severity::Warning);
+
+    let mut parts = Vec::new();
+    if error_count > 0 {
+        parts.push(format!("{error_count} error(s)"));
+    }
+    if strict && warning_count > 0 {
+        parts.push(format!("{warning_count} warning(s)"));
+    }
+    (!parts.is_empty()).then(|| format!("Validation failed with {}.", parts.join(" and ")))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Severity {
+pub(crate) enum Severity {
     Error,
     Warning,
+    /// Reported but never fails validation, not even under `--strict`.
+    Info,
 }
 
 #[derive(Debug, Clone)]
-struct Finding {
+pub(crate) struct Finding {
     path: PathBuf,
     message: String,
     severity: Severity,
 }
 
-fn collect_findings(claude_root: &Path, layout: &ClaudePaths) -> Result<Vec<Finding>> {
+pub(crate) fn collect_findings(
+    claude_root: &Path,
+    layout: &ClaudePaths,
+    config: &KbConfig,
+) -> Result<Vec<Finding>> {
     let mut findings = Vec::new();
+    let mut entries = Vec::new();
 
     for entry_result in walk_kb_documents(claude_root) {
         match entry_result {
@@ -87,6 +111,7 @@ fn collect_findings(claude_root: &Path, layout: &ClaudePaths) -> Result<Vec<Find
 
                 let errors = validate_document(path, claude_root, layout, &entry.document)?;
                 findings.extend(errors);
+                entries.push(entry);
             }
             Err(error) => {
                 let error_msg = format!("{error:#}");
@@ -99,9 +124,176 @@ fn collect_findings(claude_root: &Path, layout: &ClaudePaths) -> Result<Vec<Find
         }
     }
 
+    findings.extend(validate_link_slugs(config, &entries));
+    findings.extend(validate_unique_links(claude_root, &entries));
+    findings.extend(validate_wikilinks(config, &entries));
+    findings.extend(validate_markdown_links(&entries));
+
     Ok(findings)
 }
 
+/// Links must follow the title; a disambiguation suffix only counts when another document
+/// owns the plain slug.
+fn validate_link_slugs(config: &KbConfig, entries: &[DocumentEntry]) -> Vec<Finding> {
+    let links: HashSet<&str> = entries
+        .iter()
+        .map(|entry| entry.document.front_matter.link.as_str())
+        .collect();
+
+    entries
+        .iter()
+        .filter(|entry| {
+            !entry
+                .document
+                .front_matter
+                .is_link_consistent(config.slug_policy, &links)
+        })
+        .map(|entry| {
+            let front = &entry.document.front_matter;
+            warning(
+                &entry.path,
+                &format!(
+                    "`link` `{}` should match slugified title `{}`",
+                    front.link,
+                    front.slug_from_title(config.slug_policy)
+                ),
+            )
+        })
+        .collect()
+}
+
+fn validate_unique_links(claude_root: &Path, entries: &[DocumentEntry]) -> Vec<Finding> {
+    let workspace = claude_root.parent().unwrap_or(claude_root);
+    let mut by_link: BTreeMap<&str, Vec<&Path>> = BTreeMap::new();
+    for entry in entries {
+        by_link
+            .entry(entry.document.front_matter.link.as_str())
+            .or_default()
+            .push(&entry.path);
+    }
+
+    let mut findings = Vec::new();
+    for (link, paths) in by_link.into_iter().filter(|(_, paths)| paths.len() > 1) {
+        for path in &paths {
+            let others = paths
+                .iter()
+                .filter(|other| *other != path)
+                .map(|other| display_relative(workspace, other))
+                .collect::<Vec<_>>()
+                .join(", ");
+            findings.push(warning(
+                path,
+                &format!("`link` `{link}` is also used by {others}; references are ambiguous"),
+            ));
+        }
+    }
+    findings
+}
+
+fn validate_wikilinks(config: &KbConfig, entries: &[DocumentEntry]) -> Vec<Finding> {
+    let graph = KbGraph::from_entries(entries, config.slug_policy);
+    let outlines = outlines(entries);
+    let mut findings = Vec::new();
+
+    for entry in entries {
+        for wikilink in wikilinks(&entry.document.body) {
+            let target_path = if wikilink.target.is_empty() {
+                Some(entry.path.as_path())
+            } else {
+                graph
+                    .resolve(&wikilink.target)
+                    .and_then(|link| graph.node(link))
+                    .map(|node| node.path.as_path())
+            };
+            let Some(target_path) = target_path else {
+                findings.push(warning(
+                    &entry.path,
+                    &format!(
+                        "Wikilink `[[{}]]` on body line {} does not resolve to any document",
+                        wikilink.target, wikilink.line
+                    ),
+                ));
+                continue;
+            };
+
+            let Some(heading) = wikilink.heading.as_deref() else {
+                continue;
+            };
+            if outlines
+                .get(target_path)
+                .is_some_and(|headings| has_anchor(headings, heading))
+            {
+                continue;
+            }
+            findings.push(warning(
+                &entry.path,
+                &format!(
+                    "Wikilink `[[{}#{heading}]]` on body line {} points to a missing heading",
+                    wikilink.target, wikilink.line
+                ),
+            ));
+        }
+    }
+
+    findings
+}
+
+fn validate_markdown_links(entries: &[DocumentEntry]) -> Vec<Finding> {
+    let outlines = outlines(entries);
+    let mut findings = Vec::new();
+
+    for entry in entries {
+        for link in markdown_links(&entry.document.body) {
+            let path = link.path();
+            if link.is_external() || path.starts_with('/') {
+                continue;
+            }
+
+            let target = if path.is_empty() {
+                entry.path.clone()
+            } else {
+                resolve_relative_link(&entry.path, &path)
+            };
+            if !path.is_empty() && !target.exists() {
+                let kind = if link.is_image { "Image" } else { "Link" };
+                findings.push(warning(
+                    &entry.path,
+                    &format!(
+                        "{kind} `{}` on body line {} points to a missing file",
+                        link.destination, link.line
+                    ),
+                ));
+                continue;
+            }
+
+            let (Some(fragment), Some(headings)) =
+                (link.fragment(), outlines.get(target.as_path()))
+            else {
+                continue;
+            };
+            if fragment.is_empty() || has_anchor(headings, fragment) {
+                continue;
+            }
+            findings.push(warning(
+                &entry.path,
+                &format!(
+                    "Link `{}` on body line {} points to a missing heading",
+                    link.destination, link.line
+                ),
+            ));
+        }
+    }
+
+    findings
+}
+
+fn outlines(entries: &[DocumentEntry]) -> HashMap<&Path, Vec<Heading>> {
+    entries
+        .iter()
+        .map(|entry| (entry.path.as_path(), outline(&entry.document.body)))
+        .collect()
+}
+
 fn validate_document(
     path: &Path,
     claude_root: &Path,
@@ -153,17 +345,18 @@ fn validate_document(
         ));
     }
 
-    if !front.is_link_consistent() {
-        findings.push(warning(
+    if front.schema_version < CURRENT_SCHEMA_VERSION {
+        findings.push(info(
             path,
             &format!(
-                "`link` `{}` should match slugified title `{}`",
-                front.link,
-                front.slug_from_title()
+                "Front matter schema version {} is older than {CURRENT_SCHEMA_VERSION}; run `kb-claude migrate`",
+                front.schema_version
             ),
         ));
     }
 
+    findings.extend(validate_timestamps(path, document));
+
     if let Some(type_dir) = type_directory_name(path, claude_root) {
         if type_dir != front.doc_type {
             findings.push(error(
@@ -179,6 +372,46 @@ fn validate_document(
     Ok(findings)
 }
 
+fn validate_timestamps(path: &Path, document: &Document) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let front = &document.front_matter;
+    let now = Utc::now();
+
+    if front.created_at > now {
+        findings.push(error(
+            path,
+            &format!(
+                "`created_at` {} is in the future",
+                iso8601::format(&front.created_at)
+            ),
+        ));
+    }
+
+    if let Some(updated_at) = front.updated_at {
+        if updated_at > now {
+            findings.push(error(
+                path,
+                &format!(
+                    "`updated_at` {} is in the future",
+                    iso8601::format(&updated_at)
+                ),
+            ));
+        }
+        if updated_at < front.created_at {
+            findings.push(error(
+                path,
+                &format!(
+                    "`updated_at` {} precedes `created_at` {}",
+                    iso8601::format(&updated_at),
+                    iso8601::format(&front.created_at)
+                ),
+            ));
+        }
+    }
+
+    findings
+}
+
 fn is_hidden(path: &Path) -> bool {
     if let Some(parent) = path.parent() {
         if parent
@@ -195,7 +428,7 @@ fn is_hidden(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
-fn type_directory_name(path: &Path, claude_root: &Path) -> Option<String> {
+pub(crate) fn type_directory_name(path: &Path, claude_root: &Path) -> Option<String> {
     let relative = path.strip_prefix(claude_root).ok()?;
     let mut components = relative.components();
     components
@@ -203,15 +436,14 @@ fn type_directory_name(path: &Path, claude_root: &Path) -> Option<String> {
         .and_then(|component| component.as_os_str().to_str().map(ToString::to_string))
 }
 
-fn print_findings(findings: &[Finding], workspace: &Path) {
-    for finding in findings {
-        let label = match finding.severity {
-            Severity::Error => "error",
-            Severity::Warning => "warning",
-        };
-        let display = display_relative(workspace, &finding.path);
-        println!("{label}: {display} — {}", finding.message);
-    }
+pub(crate) fn format_finding(finding: &Finding, workspace: &Path) -> String {
+    let label = match finding.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    };
+    let display = display_relative(workspace, &finding.path);
+    format!("{label}: {display} — {}", finding.message)
 }
 
 fn error(path: &Path, message: &str) -> Finding {
@@ -229,3 +461,11 @@ fn warning(path: &Path, message: &str) -> Finding {
         severity: Severity::Warning,
     }
 }
+
+fn info(path: &Path, message: &str) -> Finding {
+    Finding {
+        path: path.to_path_buf(),
+        message: message.to_string(),
+        severity: Severity::Info,
+    }
+}
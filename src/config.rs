@@ -0,0 +1,34 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::fs::ClaudePaths;
+use crate::model::SlugPolicy;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KbConfig {
+    pub slug_policy: SlugPolicy,
+    pub backlinks_section: bool,
+    pub display_timezone: Option<Tz>,
+}
+
+impl KbConfig {
+    pub fn load(claude_root: &Path) -> Result<Self> {
+        let path = ClaudePaths::new(claude_root).config_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let raw = fs::read_to_string(&path)
+            .with_context(|| format!("Unable to read {}", path.display()))?;
+        if raw.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        serde_yaml::from_str(&raw).with_context(|| format!("Unable to parse {}", path.display()))
+    }
+}
@@ -6,6 +6,10 @@ use walkdir::WalkDir;
 
 pub const CLAUDE_ROOT: &str = ".claude";
 pub const MANIFEST_FILE: &str = "manifest.md";
+pub const CONFIG_FILE: &str = "config.yml";
+pub const INDEX_FILE: &str = "index.md";
+pub const TEMPLATES_DIR: &str = "templates";
+pub const INDEX_MARKER: &str = "<!-- kb-claude:index -->";
 pub const CLAUDE_DIRECTORIES: &[&str] = &[
     "metadata",
     "debug_history",
@@ -43,6 +47,14 @@ impl ClaudePaths {
         self.root.join(MANIFEST_FILE)
     }
 
+    pub fn manifest_template_path(&self) -> PathBuf {
+        self.root.join(TEMPLATES_DIR).join(MANIFEST_FILE)
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        self.root.join(CONFIG_FILE)
+    }
+
     pub fn type_directory(&self, doc_type: &str) -> PathBuf {
         self.root.join(doc_type)
     }
@@ -118,14 +130,59 @@ pub fn display_relative(workspace: &Path, path: &Path) -> String {
     }
 }
 
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+pub fn resolve_relative_link(document: &Path, destination: &str) -> PathBuf {
+    let base = document.parent().unwrap_or_else(|| Path::new(""));
+    normalize_path(&base.join(destination))
+}
+
+pub fn relative_link(from: &Path, to: &Path) -> String {
+    let from_dir: Vec<Component> = from
+        .parent()
+        .map(|parent| parent.components().collect())
+        .unwrap_or_default();
+    let target: Vec<Component> = to.components().collect();
+
+    let shared = from_dir
+        .iter()
+        .zip(target.iter())
+        .take_while(|(left, right)| left == right)
+        .count();
+
+    let mut parts: Vec<String> = Vec::new();
+    for _ in shared..from_dir.len() {
+        parts.push("..".to_string());
+    }
+    for component in &target[shared..] {
+        parts.push(component.as_os_str().to_string_lossy().into_owned());
+    }
+    parts.join("/")
+}
+
 #[derive(Debug)]
 pub struct DocumentEntry {
     pub path: PathBuf,
     pub document: crate::model::Document,
 }
 
-pub fn walk_kb_documents(claude_root: &Path) -> impl Iterator<Item = Result<DocumentEntry>> + '_ {
+pub fn walk_kb_files(claude_root: &Path) -> impl Iterator<Item = Result<PathBuf>> + '_ {
     WalkDir::new(claude_root)
+        .sort_by_file_name()
         .into_iter()
         .filter_map(move |entry| {
             let entry = match entry {
@@ -142,6 +199,10 @@ pub fn walk_kb_documents(claude_root: &Path) -> impl Iterator<Item = Result<Docu
                 return None;
             }
 
+            if path.file_name().is_some_and(|name| name == INDEX_FILE) && is_generated_index(path) {
+                return None;
+            }
+
             if is_ignored_path(path, claude_root) {
                 return None;
             }
@@ -150,31 +211,24 @@ pub fn walk_kb_documents(claude_root: &Path) -> impl Iterator<Item = Result<Docu
                 return None;
             }
 
-            let content = match fs::read_to_string(path) {
-                Ok(c) => c,
-                Err(e) => {
-                    return Some(Err(anyhow::anyhow!(
-                        "Unable to read {}: {}",
-                        path.display(),
-                        e
-                    )));
-                }
-            };
+            Some(Ok(path.to_path_buf()))
+        })
+}
 
-            let document = match crate::model::Document::parse(&content) {
-                Ok(doc) => doc,
-                Err(e) => {
-                    return Some(Err(anyhow::anyhow!(
-                        "Unable to parse {}: {}",
-                        path.display(),
-                        e
-                    )));
-                }
-            };
+pub fn is_generated_index(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|content| content.starts_with(INDEX_MARKER))
+}
 
-            Some(Ok(DocumentEntry {
-                path: path.to_path_buf(),
-                document,
-            }))
-        })
+pub fn walk_kb_documents(claude_root: &Path) -> impl Iterator<Item = Result<DocumentEntry>> + '_ {
+    walk_kb_files(claude_root).map(|path_result| {
+        let path = path_result?;
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("Unable to read {}: {}", path.display(), e))?;
+
+        let document = crate::model::Document::parse(&content)
+            .map_err(|e| anyhow::anyhow!("Unable to parse {}: {}", path.display(), e))?;
+
+        Ok(DocumentEntry { path, document })
+    })
 }
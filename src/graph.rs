@@ -0,0 +1,268 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::config::KbConfig;
+use crate::fs::{walk_kb_documents, DocumentEntry};
+use crate::markdown::wikilinks;
+use crate::model::{normalize_relation_target, slugify_with, SlugPolicy};
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct GraphNode {
+    pub link: String,
+    pub title: String,
+    #[serde(rename = "type")]
+    pub doc_type: String,
+    pub tags: Vec<String>,
+    pub uuid: Uuid,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+    Relation,
+    Wikilink,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    pub kind: EdgeKind,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KbGraph {
+    nodes: BTreeMap<String, GraphNode>,
+    edges: BTreeSet<GraphEdge>,
+    outgoing: BTreeMap<String, BTreeSet<String>>,
+    incoming: BTreeMap<String, BTreeSet<String>>,
+    slug_policy: SlugPolicy,
+}
+
+impl KbGraph {
+    pub fn load(claude_root: &Path) -> Result<Self> {
+        let config = KbConfig::load(claude_root)?;
+        let entries = walk_kb_documents(claude_root).collect::<Result<Vec<_>>>()?;
+        Ok(Self::from_entries(&entries, config.slug_policy))
+    }
+
+    pub fn from_entries(entries: &[DocumentEntry], slug_policy: SlugPolicy) -> Self {
+        let mut graph = Self {
+            slug_policy,
+            ..Self::default()
+        };
+
+        for entry in entries {
+            let front = &entry.document.front_matter;
+            graph
+                .nodes
+                .entry(front.link.clone())
+                .or_insert_with(|| GraphNode {
+                    link: front.link.clone(),
+                    title: front.title.clone(),
+                    doc_type: front.doc_type.clone(),
+                    tags: front.tags.clone(),
+                    uuid: front.uuid,
+                    path: entry.path.clone(),
+                });
+        }
+
+        for entry in entries {
+            let front = &entry.document.front_matter;
+            for relation in &front.ontological_relations {
+                graph.add_edge(&front.link, &relation.relates_to, EdgeKind::Relation);
+            }
+        }
+
+        for entry in entries {
+            let front = &entry.document.front_matter;
+            for wikilink in wikilinks(&entry.document.body) {
+                graph.add_edge(&front.link, &wikilink.target, EdgeKind::Wikilink);
+            }
+        }
+
+        graph
+    }
+
+    fn add_edge(&mut self, source: &str, reference: &str, kind: EdgeKind) {
+        let Some(target) = self.resolve(reference).map(ToString::to_string) else {
+            return;
+        };
+        if target == source || self.has_edge(source, &target) {
+            return;
+        }
+        self.outgoing
+            .entry(source.to_string())
+            .or_default()
+            .insert(target.clone());
+        self.incoming
+            .entry(target.clone())
+            .or_default()
+            .insert(source.to_string());
+        self.edges.insert(GraphEdge {
+            source: source.to_string(),
+            target,
+            kind,
+        });
+    }
+
+    /// Resolves a relation or wikilink target to a document link, accepting the exact link,
+    /// `[[link]]`, a document uuid, or a title that slugifies to the link.
+    pub fn resolve<'a>(&'a self, reference: &str) -> Option<&'a str> {
+        let reference = normalize_relation_target(reference);
+        if reference.is_empty() {
+            return None;
+        }
+        if let Some((link, _)) = self.nodes.get_key_value(&reference) {
+            return Some(link);
+        }
+        if let Ok(uuid) = Uuid::parse_str(&reference) {
+            return self
+                .nodes()
+                .find(|node| node.uuid == uuid)
+                .map(|node| node.link.as_str());
+        }
+        self.nodes
+            .get_key_value(&slugify_with(&reference, self.slug_policy))
+            .map(|(link, _)| link.as_str())
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &GraphNode> {
+        self.nodes.values()
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = &GraphEdge> {
+        self.edges.iter()
+    }
+
+    pub fn node(&self, link: &str) -> Option<&GraphNode> {
+        self.nodes.get(link)
+    }
+
+    pub fn contains(&self, link: &str) -> bool {
+        self.nodes.contains_key(link)
+    }
+
+    pub fn outgoing<'a>(&'a self, link: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.outgoing
+            .get(link)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    pub fn incoming<'a>(&'a self, link: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.incoming
+            .get(link)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    pub fn has_edge(&self, source: &str, target: &str) -> bool {
+        self.outgoing
+            .get(source)
+            .is_some_and(|targets| targets.contains(target))
+    }
+
+    pub fn neighbors<'a>(&'a self, link: &'a str) -> BTreeSet<&'a str> {
+        self.outgoing(link).chain(self.incoming(link)).collect()
+    }
+
+    pub fn degree(&self, link: &str) -> usize {
+        self.neighbors(link).len()
+    }
+
+    pub fn orphans(&self) -> impl Iterator<Item = &GraphNode> {
+        self.nodes().filter(|node| self.degree(&node.link) == 0)
+    }
+
+    pub fn connected_components(&self) -> Vec<Vec<String>> {
+        let mut seen = BTreeSet::new();
+        let mut components = Vec::new();
+
+        for link in self.nodes.keys() {
+            if seen.contains(link) {
+                continue;
+            }
+            let component = self.neighborhood(link, usize::MAX);
+            seen.extend(component.iter().cloned());
+            components.push(component.into_iter().collect::<Vec<_>>());
+        }
+
+        components.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        components
+    }
+
+    pub fn neighborhood(&self, link: &str, depth: usize) -> BTreeSet<String> {
+        self.distances_from(link, depth).into_keys().collect()
+    }
+
+    pub fn distances_from(&self, link: &str, depth: usize) -> BTreeMap<String, usize> {
+        let mut distances = BTreeMap::new();
+        if !self.contains(link) {
+            return distances;
+        }
+
+        let mut queue = VecDeque::from([(link.to_string(), 0)]);
+        distances.insert(link.to_string(), 0);
+        while let Some((current, distance)) = queue.pop_front() {
+            if distance == depth {
+                continue;
+            }
+            for neighbor in self.neighbors(&current) {
+                if !distances.contains_key(neighbor) {
+                    distances.insert(neighbor.to_string(), distance + 1);
+                    queue.push_back((neighbor.to_string(), distance + 1));
+                }
+            }
+        }
+        distances
+    }
+
+    pub fn shortest_path(&self, source: &str, target: &str) -> Option<Vec<String>> {
+        if !self.contains(source) || !self.contains(target) {
+            return None;
+        }
+
+        let mut previous: BTreeMap<String, String> = BTreeMap::new();
+        let mut visited = BTreeSet::from([source.to_string()]);
+        let mut queue = VecDeque::from([source.to_string()]);
+
+        while let Some(current) = queue.pop_front() {
+            if current == target {
+                let mut path = vec![current];
+                while let Some(prior) = previous.get(path.last()?) {
+                    path.push(prior.clone());
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for neighbor in self.neighbors(&current) {
+                if visited.insert(neighbor.to_string()) {
+                    previous.insert(neighbor.to_string(), current.clone());
+                    queue.push_back(neighbor.to_string());
+                }
+            }
+        }
+        None
+    }
+
+    pub fn retain(&mut self, mut keep: impl FnMut(&GraphNode) -> bool) {
+        self.nodes.retain(|_, node| keep(node));
+        let nodes = &self.nodes;
+        self.edges
+            .retain(|edge| nodes.contains_key(&edge.source) && nodes.contains_key(&edge.target));
+        for adjacency in [&mut self.outgoing, &mut self.incoming] {
+            adjacency.retain(|link, _| nodes.contains_key(link));
+            for links in adjacency.values_mut() {
+                links.retain(|link| nodes.contains_key(link));
+            }
+        }
+    }
+}
@@ -1,3 +1,10 @@
 pub mod cli;
+pub mod config;
 pub mod fs;
+pub mod graph;
+pub mod markdown;
 pub mod model;
+pub mod schema;
+pub mod similarity;
+pub mod site;
+pub mod template;
@@ -0,0 +1,586 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::ops::Range;
+
+const MARKER_PREFIX: &str = "<!-- kb-claude:";
+
+pub const BACKLINKS_SECTION: &str = "backlinks";
+pub const TOC_SECTION: &str = "toc";
+
+fn start_marker(name: &str) -> String {
+    format!("{MARKER_PREFIX}{name}:start -->")
+}
+
+fn end_marker(name: &str) -> String {
+    format!("{MARKER_PREFIX}{name}:end -->")
+}
+
+fn marked_range(body: &str, name: &str) -> Option<(usize, usize)> {
+    let start = body.find(&start_marker(name))?;
+    let end_marker = end_marker(name);
+    let end = body[start..].find(&end_marker)? + start + end_marker.len();
+    Some((start, end))
+}
+
+pub fn strip_marked_section(body: &str, name: &str) -> String {
+    match marked_range(body, name) {
+        Some((start, end)) => {
+            let before = body[..start].trim_end();
+            let after = body[end..].trim_start_matches(['\r', '\n']);
+            match (before.is_empty(), after.is_empty()) {
+                (true, _) => after.to_string(),
+                (false, true) => before.to_string(),
+                (false, false) => format!("{before}\n\n{after}"),
+            }
+        }
+        None => body.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    Start,
+    End,
+}
+
+pub fn replace_marked_section(
+    body: &str,
+    name: &str,
+    content: Option<&str>,
+    placement: Placement,
+) -> String {
+    let Some(content) = content else {
+        return strip_marked_section(body, name);
+    };
+
+    let section = format!(
+        "{}\n{}\n{}",
+        start_marker(name),
+        content.trim_end(),
+        end_marker(name)
+    );
+
+    match marked_range(body, name) {
+        Some((start, end)) => format!("{}{section}{}", &body[..start], &body[end..]),
+        None if body.trim().is_empty() => section,
+        None => match placement {
+            Placement::Start => format!("{section}\n\n{}", body.trim_start_matches(['\r', '\n'])),
+            Placement::End => format!("{}\n\n{section}", body.trim_end()),
+        },
+    }
+}
+
+pub fn escape_table_cell(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for line in value
+        .split(['\n', '\r'])
+        .filter(|line| !line.trim().is_empty())
+    {
+        if !escaped.is_empty() {
+            escaped.push(' ');
+        }
+        for ch in line.trim().chars() {
+            if matches!(ch, '\\' | '|' | '`' | '[' | ']' | '<') {
+                escaped.push('\\');
+            }
+            escaped.push(ch);
+        }
+    }
+    escaped
+}
+
+pub fn escape_link_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, '\\' | '[' | ']' | '(' | ')') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+pub fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+pub fn summarize(body: &str, max_chars: usize) -> Option<String> {
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut generated = false;
+    let mut previous = 0;
+
+    for line in prose_lines(body) {
+        let after_fence = line.number != previous + 1;
+        previous = line.number;
+        if after_fence && !paragraph.is_empty() {
+            break;
+        }
+        let original = body[line.offset..]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim();
+        if original.starts_with(MARKER_PREFIX) {
+            generated = original.ends_with(":start -->");
+            if !paragraph.is_empty() {
+                break;
+            }
+            continue;
+        }
+        if generated {
+            continue;
+        }
+
+        let is_block = original.is_empty()
+            || original.starts_with('#')
+            || original.starts_with('|')
+            || original.starts_with("<!--")
+            || original.starts_with("![");
+        if is_block {
+            if paragraph.is_empty() {
+                continue;
+            }
+            break;
+        }
+
+        let mut text = original;
+        while let Some(rest) = ["> ", "- ", "* ", "+ "]
+            .iter()
+            .find_map(|marker| text.strip_prefix(marker))
+        {
+            text = rest.trim_start();
+        }
+        let text = match text.split_once(". ") {
+            Some((number, rest)) if number.chars().all(|ch| ch.is_ascii_digit()) => rest,
+            _ => text,
+        };
+        paragraph.push(text);
+    }
+
+    let text = paragraph.join(" ");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() <= max_chars {
+        return Some(text);
+    }
+
+    let sentence_end = text
+        .match_indices(['.', '!', '?'])
+        .map(|(index, _)| index + 1)
+        .find(|end| text[*end..].starts_with(' '))
+        .filter(|end| text[..*end].chars().count() <= max_chars);
+    if let Some(end) = sentence_end {
+        return Some(text[..end].to_string());
+    }
+
+    let mut truncated: String = text.chars().take(max_chars).collect();
+    if let Some(space) = truncated.rfind(' ') {
+        truncated.truncate(space);
+    }
+    Some(format!(
+        "{}…",
+        truncated.trim_end_matches([',', ';', ':', ' '])
+    ))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    pub level: usize,
+    pub text: String,
+    pub anchor: String,
+    pub line: usize,
+}
+
+pub fn outline(body: &str) -> Vec<Heading> {
+    let mut headings = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for line in prose_lines(body) {
+        let trimmed = line.text.trim_start();
+        if line.text.len() - trimmed.len() > 3 {
+            continue;
+        }
+        let level = trimmed.chars().take_while(|ch| *ch == '#').count();
+        if level == 0 || level > 6 {
+            continue;
+        }
+        let rest = &trimmed[level..];
+        if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+            continue;
+        }
+
+        let original = body[line.offset..]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim_start();
+        let text = original[level..]
+            .trim()
+            .trim_end_matches('#')
+            .trim_end()
+            .to_string();
+
+        let base = heading_anchor(&text);
+        let count = seen.entry(base.clone()).or_insert(0);
+        let anchor = if *count == 0 {
+            base.clone()
+        } else {
+            format!("{base}-{count}")
+        };
+        *count += 1;
+
+        headings.push(Heading {
+            level,
+            text,
+            anchor,
+            line: line.number,
+        });
+    }
+
+    headings
+}
+
+pub fn heading_anchor(text: &str) -> String {
+    text.trim()
+        .chars()
+        .filter_map(|ch| {
+            if ch.is_alphanumeric() || ch == '-' || ch == '_' {
+                Some(ch.to_lowercase().collect::<String>())
+            } else if ch == ' ' {
+                Some("-".to_string())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+pub fn has_anchor(headings: &[Heading], fragment: &str) -> bool {
+    let decoded = percent_decode(fragment);
+    let wanted = heading_anchor(&decoded);
+    headings
+        .iter()
+        .any(|heading| heading.anchor == decoded || heading.anchor == wanted)
+}
+
+pub fn render_toc(headings: &[Heading]) -> Option<String> {
+    let base_level = headings.iter().map(|heading| heading.level).min()?;
+    let mut lines = vec!["## Contents".to_string(), String::new()];
+    for heading in headings {
+        lines.push(format!(
+            "{}- [{}](#{})",
+            "  ".repeat(heading.level - base_level),
+            heading.text,
+            heading.anchor
+        ));
+    }
+    Some(lines.join("\n"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WikiLink {
+    pub target: String,
+    pub heading: Option<String>,
+    pub label: Option<String>,
+    pub line: usize,
+    /// Byte range of the text between `[[` and `]]` within the body.
+    pub span: Range<usize>,
+}
+
+pub fn wikilinks(body: &str) -> Vec<WikiLink> {
+    let mut links = Vec::new();
+
+    for line in prose_lines(body) {
+        let mut cursor = 0;
+        while let Some(found) = line.text[cursor..].find("[[") {
+            let inner_start = cursor + found + 2;
+            let Some(length) = line.text[inner_start..].find("]]") else {
+                break;
+            };
+            let inner = &line.text[inner_start..inner_start + length];
+            let span = line.offset + inner_start..line.offset + inner_start + length;
+            if let Some(link) = parse_wikilink(inner, line.number, span) {
+                links.push(link);
+            }
+            cursor = inner_start + length + 2;
+        }
+    }
+
+    links
+}
+
+fn parse_wikilink(inner: &str, line: usize, span: Range<usize>) -> Option<WikiLink> {
+    if inner.contains('[') {
+        return None;
+    }
+
+    let (reference, label) = match inner.split_once('|') {
+        Some((reference, label)) => (reference, Some(label.trim().to_string())),
+        None => (inner, None),
+    };
+    let (target, heading) = match reference.split_once('#') {
+        Some((target, heading)) => (target, Some(heading.trim().to_string())),
+        None => (reference, None),
+    };
+
+    let target = target.trim().to_string();
+    if target.is_empty() && heading.is_none() {
+        return None;
+    }
+
+    Some(WikiLink {
+        target,
+        heading,
+        label: label.filter(|label| !label.is_empty()),
+        line,
+        span,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownLink {
+    pub destination: String,
+    pub is_image: bool,
+    pub line: usize,
+    pub span: Range<usize>,
+}
+
+impl MarkdownLink {
+    pub fn is_external(&self) -> bool {
+        is_external_destination(&self.destination)
+    }
+
+    pub fn path(&self) -> String {
+        let end = self
+            .destination
+            .find(['#', '?'])
+            .unwrap_or(self.destination.len());
+        percent_decode(&self.destination[..end])
+    }
+
+    pub fn fragment(&self) -> Option<&str> {
+        self.destination
+            .split_once('#')
+            .map(|(_, fragment)| fragment)
+    }
+}
+
+pub fn is_external_destination(destination: &str) -> bool {
+    if destination.starts_with("//") {
+        return true;
+    }
+    match destination.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.len() > 1
+                && scheme.starts_with(|ch: char| ch.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+pub fn markdown_links(body: &str) -> Vec<MarkdownLink> {
+    let mut links = Vec::new();
+
+    for line in prose_lines(body) {
+        if let Some(link) = reference_definition(&line) {
+            links.push(link);
+            continue;
+        }
+
+        let text = line.text.as_str();
+        let mut cursor = 0;
+        while let Some(found) = text[cursor..].find('[') {
+            let open = cursor + found;
+            if text[open..].starts_with("[[") {
+                cursor = text[open..]
+                    .find("]]")
+                    .map(|end| open + end + 2)
+                    .unwrap_or(open + 2);
+                continue;
+            }
+            let Some(close) = text[open + 1..].find(']').map(|end| open + 1 + end) else {
+                break;
+            };
+            if !text[close + 1..].starts_with('(') {
+                cursor = open + 1;
+                continue;
+            }
+            let inner_start = close + 2;
+            let Some(length) = closing_paren(&text[inner_start..]) else {
+                break;
+            };
+            if let Some((start, end)) = destination_bounds(&text[inner_start..inner_start + length])
+            {
+                let absolute = line.offset + inner_start;
+                links.push(MarkdownLink {
+                    destination: text[inner_start + start..inner_start + end].to_string(),
+                    is_image: text[..open].ends_with('!'),
+                    line: line.number,
+                    span: absolute + start..absolute + end,
+                });
+            }
+            cursor = inner_start + length + 1;
+        }
+    }
+
+    links
+}
+
+fn reference_definition(line: &ProseLine) -> Option<MarkdownLink> {
+    let indent = line.text.len() - line.text.trim_start().len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = line.text[indent..].strip_prefix('[')?;
+    let close = rest.find("]:")?;
+    if close == 0 || rest[..close].contains('[') {
+        return None;
+    }
+    let after = indent + 1 + close + 2;
+    let (start, end) = destination_bounds(&line.text[after..])?;
+    Some(MarkdownLink {
+        destination: line.text[after + start..after + end].to_string(),
+        is_image: false,
+        line: line.number,
+        span: line.offset + after + start..line.offset + after + end,
+    })
+}
+
+fn closing_paren(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, ch) in text.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(index),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn destination_bounds(inner: &str) -> Option<(usize, usize)> {
+    let start = inner.len() - inner.trim_start().len();
+    let rest = &inner[start..];
+    if let Some(wrapped) = rest.strip_prefix('<') {
+        let end = wrapped.find('>')?;
+        return Some((start + 1, start + 1 + end));
+    }
+    let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+    (end > 0).then_some((start, start + end))
+}
+
+/// Rewrites the given byte ranges of `body`; ranges must not overlap.
+pub fn apply_edits(body: &str, mut edits: Vec<(Range<usize>, String)>) -> String {
+    edits.sort_by_key(|(range, _)| Reverse(range.start));
+    let mut output = body.to_string();
+    for (range, replacement) in edits {
+        output.replace_range(range, &replacement);
+    }
+    output
+}
+
+pub fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%'
+            && index + 2 < bytes.len()
+            && bytes[index + 1].is_ascii_hexdigit()
+            && bytes[index + 2].is_ascii_hexdigit()
+        {
+            if let Ok(byte) = u8::from_str_radix(&value[index + 1..index + 3], 16) {
+                decoded.push(byte);
+                index += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+pub fn percent_encode_path(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29")
+}
+
+/// A body line outside fenced code blocks, with inline code spans blanked out. Blanking keeps
+/// byte offsets intact so spans computed on `text` apply to the original body.
+pub struct ProseLine {
+    pub number: usize,
+    pub offset: usize,
+    pub text: String,
+}
+
+pub fn prose_lines(body: &str) -> Vec<ProseLine> {
+    let mut lines = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut offset = 0;
+
+    for (index, raw) in body.split_inclusive('\n').enumerate() {
+        let line_offset = offset;
+        offset += raw.len();
+        let line = raw.trim_end_matches(['\n', '\r']);
+
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            fence = Some("```");
+            continue;
+        }
+        if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+            continue;
+        }
+        lines.push(ProseLine {
+            number: index + 1,
+            offset: line_offset,
+            text: blank_inline_code(line),
+        });
+    }
+
+    lines
+}
+
+fn blank_inline_code(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut in_code = false;
+    for ch in line.chars() {
+        if ch == '`' {
+            in_code = !in_code;
+            output.push(ch);
+        } else if in_code {
+            output.extend(std::iter::repeat_n(' ', ch.len_utf8()));
+        } else {
+            output.push(ch);
+        }
+    }
+    output
+}
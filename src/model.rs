@@ -1,31 +1,91 @@
-use anyhow::{anyhow, Context, Result};
+use std::collections::HashSet;
+
+use anyhow::{anyhow, bail, Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 pub const FRONT_MATTER_DELIMITER: &str = "---";
-const FRONT_MATTER_BLOCK_BREAK: &str = "\n---\n";
+const FRONT_MATTER_BLOCK_BREAK: &str = "\n---";
+
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+pub const LEGACY_SCHEMA_VERSION: u32 = 1;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct OntologicalRelation {
+    #[serde(deserialize_with = "deserialize_relation_target")]
     pub relates_to: String,
 }
 
+pub fn normalize_relation_target(raw: &str) -> String {
+    let mut value = raw.trim();
+    if let Some(inner) = value
+        .strip_prefix("[[")
+        .and_then(|rest| rest.strip_suffix("]]"))
+    {
+        value = inner;
+    }
+    let value = value.split(['|', '#']).next().unwrap_or_default().trim();
+
+    match Uuid::parse_str(value) {
+        Ok(uuid) => uuid.hyphenated().to_string(),
+        Err(_) => value.to_string(),
+    }
+}
+
+/// Accepts `slug`, `"[[slug]]"` and the unquoted `[[slug]]` that YAML reads as a nested list.
+fn deserialize_relation_target<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = serde_yaml::Value::deserialize(deserializer)?;
+    relation_target_from_yaml(&value)
+        .map(|target| normalize_relation_target(&target))
+        .ok_or_else(|| {
+            serde::de::Error::custom("`relates_to` must be a link, `[[link]]` or a document uuid")
+        })
+}
+
+pub(crate) fn relation_target_from_yaml(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(target) => Some(target.clone()),
+        serde_yaml::Value::Sequence(items) if items.len() == 1 => {
+            relation_target_from_yaml(&items[0])
+        }
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct DocumentFrontMatter {
     pub title: String,
     pub link: String,
     #[serde(rename = "type")]
     pub doc_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
     #[serde(default)]
     pub ontological_relations: Vec<OntologicalRelation>,
     #[serde(default)]
     pub tags: Vec<String>,
     #[serde(with = "iso8601")]
     pub created_at: DateTime<Utc>,
-    #[serde(with = "iso8601")]
-    pub updated_at: DateTime<Utc>,
+    #[serde(
+        default,
+        with = "iso8601::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
     pub uuid: Uuid,
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
+    /// Keys this CLI does not know, kept so rewriting a document does not drop them.
+    #[serde(flatten)]
+    pub extra: serde_yaml::Mapping,
+}
+
+fn legacy_schema_version() -> u32 {
+    LEGACY_SCHEMA_VERSION
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -44,28 +104,84 @@ impl DocumentFrontMatter {
             title: title_str,
             link,
             doc_type: doc_type_str,
+            summary: None,
             ontological_relations: Vec::new(),
             tags: Vec::new(),
             created_at: now,
-            updated_at: now,
+            updated_at: Some(now),
             uuid: Uuid::new_v4(),
+            schema_version: CURRENT_SCHEMA_VERSION,
+            extra: serde_yaml::Mapping::new(),
         }
     }
 
     pub fn touch_updated(&mut self) {
-        self.updated_at = Utc::now();
+        self.updated_at = Some(Utc::now());
     }
 
-    pub fn ensure_link_matches_slug(&mut self) {
-        self.link = self.slug_from_title();
+    pub fn last_updated(&self) -> DateTime<Utc> {
+        self.updated_at.unwrap_or(self.created_at)
     }
 
-    pub fn slug_from_title(&self) -> String {
-        slugify(&self.title)
+    pub fn canonicalize(&mut self) {
+        self.title = self.title.trim().to_string();
+        self.link = self.link.trim().to_string();
+        self.doc_type = self.doc_type.trim().to_string();
+        self.summary = self
+            .summary
+            .as_deref()
+            .map(str::trim)
+            .filter(|summary| !summary.is_empty())
+            .map(ToString::to_string);
+
+        let mut tags: Vec<String> = self
+            .tags
+            .iter()
+            .map(|tag| tag.trim().to_lowercase())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        tags.sort();
+        tags.dedup();
+        self.tags = tags;
+
+        for relation in &mut self.ontological_relations {
+            relation.relates_to = normalize_relation_target(&relation.relates_to);
+        }
+    }
+
+    pub fn ensure_link_matches_slug(&mut self, policy: SlugPolicy) {
+        self.link = self.slug_from_title(policy);
     }
 
-    pub fn is_link_consistent(&self) -> bool {
-        self.link == self.slug_from_title()
+    pub fn slug_from_title(&self, policy: SlugPolicy) -> String {
+        slugify_with(&self.title, policy)
+    }
+
+    /// True when `link` is the slugified title, or — when another document in `links` already
+    /// owns that slug — the slug followed by a disambiguation suffix (`-2`, `-3`, … or the
+    /// document's short uuid). Slugs from before slug policies existed (`Ascii`) still count.
+    pub fn is_link_consistent(&self, policy: SlugPolicy, links: &HashSet<&str>) -> bool {
+        [policy, SlugPolicy::Ascii].into_iter().any(|policy| {
+            let slug = self.slug_from_title(policy);
+            if self.link == slug {
+                return true;
+            }
+            if !links.contains(slug.as_str()) {
+                return false;
+            }
+            match self
+                .link
+                .strip_prefix(&slug)
+                .and_then(|rest| rest.strip_prefix('-'))
+            {
+                Some(suffix) => suffix == self.short_uuid() || is_numeric_suffix(suffix),
+                None => false,
+            }
+        })
+    }
+
+    pub fn short_uuid(&self) -> String {
+        self.uuid.simple().to_string()[..8].to_string()
     }
 }
 
@@ -78,48 +194,106 @@ impl Document {
     }
 
     pub fn parse(raw: &str) -> Result<Self> {
-        let trimmed = raw.trim_start();
-        let rest = trimmed
-            .strip_prefix(FRONT_MATTER_DELIMITER)
-            .ok_or_else(|| anyhow!("Document missing starting front matter delimiter"))?;
-
-        let rest = rest
-            .strip_prefix('\n')
-            .ok_or_else(|| anyhow!("Front matter must start on a new line"))?;
-
-        let (yaml_block, body) = rest
-            .split_once(FRONT_MATTER_BLOCK_BREAK)
-            .ok_or_else(|| anyhow!("Document missing closing front matter delimiter"))?;
+        let (yaml_block, body) = split_front_matter(raw)?;
 
         let front_matter: DocumentFrontMatter = serde_yaml::from_str(yaml_block)
             .with_context(|| "Unable to parse document front matter as YAML")?;
 
+        if front_matter.schema_version > CURRENT_SCHEMA_VERSION {
+            bail!(
+                "Front matter uses schema version {} but this kb-claude supports up to {}; upgrade the CLI",
+                front_matter.schema_version,
+                CURRENT_SCHEMA_VERSION
+            );
+        }
+
         Ok(Self {
             front_matter,
             body: body.to_string(),
         })
     }
 
+    /// Serializes the document with struct key order, LF line endings and a single trailing
+    /// newline. Front matter values are written as they are; `canonicalize` them first for
+    /// the fully canonical form `kb-claude fmt` produces.
     pub fn to_markdown(&self) -> Result<String> {
         let yaml = serde_yaml::to_string(&self.front_matter)
             .with_context(|| "Unable to serialize document front matter")?;
         let yaml_trimmed = yaml.trim_start_matches(&format!("{FRONT_MATTER_DELIMITER}\n"));
+        let body = self.body.replace("\r\n", "\n");
         Ok(format!(
             "{delim}\n{front}{delim}\n{body}\n",
             delim = FRONT_MATTER_DELIMITER,
             front = yaml_trimmed,
-            body = self.body.trim_end()
+            body = body.trim_end()
         ))
     }
 }
 
+fn is_numeric_suffix(suffix: &str) -> bool {
+    !suffix.starts_with('0')
+        && suffix.chars().all(|ch| ch.is_ascii_digit())
+        && suffix.parse::<u32>().is_ok_and(|number| number >= 2)
+}
+
+pub fn split_front_matter(raw: &str) -> Result<(&str, &str)> {
+    let trimmed = raw.trim_start();
+    let rest = trimmed
+        .strip_prefix(FRONT_MATTER_DELIMITER)
+        .ok_or_else(|| anyhow!("Document missing starting front matter delimiter"))?;
+
+    let rest = rest
+        .strip_prefix('\n')
+        .or_else(|| rest.strip_prefix("\r\n"))
+        .ok_or_else(|| anyhow!("Front matter must start on a new line"))?;
+
+    let mut offset = 0;
+    while let Some(found) = rest[offset..].find(FRONT_MATTER_BLOCK_BREAK) {
+        let start = offset + found;
+        let after = &rest[start + FRONT_MATTER_BLOCK_BREAK.len()..];
+        let body = after
+            .strip_prefix('\n')
+            .or_else(|| after.strip_prefix("\r\n"))
+            .or_else(|| after.is_empty().then_some(after));
+        if let Some(body) = body {
+            let yaml = rest[..start].strip_suffix('\r').unwrap_or(&rest[..start]);
+            return Ok((yaml, body));
+        }
+        offset = start + 1;
+    }
+
+    Err(anyhow!("Document missing closing front matter delimiter"))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlugPolicy {
+    #[default]
+    Transliterate,
+    Unicode,
+    Ascii,
+}
+
 pub fn slugify(input: &str) -> String {
+    slugify_with(input, SlugPolicy::default())
+}
+
+pub fn slugify_with(input: &str, policy: SlugPolicy) -> String {
+    let source = match policy {
+        SlugPolicy::Transliterate => deunicode::deunicode(input.trim()),
+        SlugPolicy::Unicode | SlugPolicy::Ascii => input.trim().to_string(),
+    };
+
     let mut slug = String::new();
     let mut pending_dash = false;
 
-    for ch in input.trim().chars() {
-        if ch.is_ascii_alphanumeric() {
-            slug.push(ch.to_ascii_lowercase());
+    for ch in source.chars() {
+        let keep = match policy {
+            SlugPolicy::Unicode => ch.is_alphanumeric(),
+            SlugPolicy::Transliterate | SlugPolicy::Ascii => ch.is_ascii_alphanumeric(),
+        };
+        if keep {
+            slug.extend(ch.to_lowercase());
             pending_dash = false;
         } else if matches!(ch, ' ' | '-' | '_' | '.') && !slug.is_empty() && !pending_dash {
             slug.push('-');
@@ -141,15 +315,54 @@ pub fn slugify(input: &str) -> String {
 pub mod iso8601 {
     use std::str::FromStr;
 
-    use chrono::{DateTime, SecondsFormat, Utc};
+    use anyhow::{anyhow, Result};
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
     use serde::{self, Deserialize, Deserializer, Serializer};
 
+    const NAIVE_DATETIME_FORMATS: &[&str] = &[
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ];
+
+    pub fn parse(raw: &str) -> Result<DateTime<Utc>> {
+        let value = raw.trim();
+
+        if let Ok(parsed) = DateTime::parse_from_rfc3339(value) {
+            return Ok(parsed.with_timezone(&Utc));
+        }
+
+        if let Ok(parsed) = DateTime::from_str(value) {
+            return Ok(parsed);
+        }
+
+        for format in NAIVE_DATETIME_FORMATS {
+            if let Ok(parsed) = NaiveDateTime::parse_from_str(value, format) {
+                return Ok(parsed.and_utc());
+            }
+        }
+
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            if let Some(midnight) = date.and_hms_opt(0, 0, 0) {
+                return Ok(midnight.and_utc());
+            }
+        }
+
+        Err(anyhow!(
+            "Unrecognized timestamp `{value}`; expected RFC 3339 or YYYY-MM-DD"
+        ))
+    }
+
+    pub fn format(value: &DateTime<Utc>) -> String {
+        value.to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+
     pub fn serialize<S>(value: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let formatted = value.to_rfc3339_opts(SecondsFormat::Secs, true);
-        serializer.serialize_str(&formatted)
+        serializer.serialize_str(&format(value))
     }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
@@ -157,6 +370,34 @@ pub mod iso8601 {
         D: Deserializer<'de>,
     {
         let raw = String::deserialize(deserializer)?;
-        DateTime::from_str(&raw).map_err(serde::de::Error::custom)
+        parse(&raw).map_err(serde::de::Error::custom)
+    }
+
+    pub mod option {
+        use chrono::{DateTime, Utc};
+        use serde::{self, Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S>(value: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match value {
+                Some(timestamp) => super::serialize(timestamp, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let raw = Option::<String>::deserialize(deserializer)?;
+            match raw {
+                Some(value) if !value.trim().is_empty() => super::parse(&value)
+                    .map(Some)
+                    .map_err(serde::de::Error::custom),
+                _ => Ok(None),
+            }
+        }
     }
 }
@@ -0,0 +1,146 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde_yaml::{Mapping, Value};
+
+use crate::model::{
+    normalize_relation_target, relation_target_from_yaml, split_front_matter, Document,
+    DocumentFrontMatter, CURRENT_SCHEMA_VERSION, LEGACY_SCHEMA_VERSION,
+};
+
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// A single upgrade step from `from` to `from + 1`, applied to the raw YAML mapping so that
+/// documents which no longer deserialize into [`DocumentFrontMatter`] can still be repaired.
+pub struct Migration {
+    pub from: u32,
+    pub description: &'static str,
+    apply: fn(&mut Mapping) -> Vec<String>,
+}
+
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    from: 1,
+    description: "typed relations and explicit updated_at",
+    apply: migrate_v1_to_v2,
+}];
+
+#[derive(Debug)]
+pub struct MigrationOutcome {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub changes: Vec<String>,
+    pub document: Document,
+}
+
+impl MigrationOutcome {
+    pub fn is_changed(&self) -> bool {
+        self.from_version != self.to_version
+    }
+}
+
+pub fn migrate_document(raw: &str) -> Result<MigrationOutcome> {
+    let (yaml_block, body) = split_front_matter(raw)?;
+    let value: Value = serde_yaml::from_str(yaml_block)
+        .with_context(|| "Unable to parse document front matter as YAML")?;
+    let mut mapping = match value {
+        Value::Mapping(mapping) => mapping,
+        _ => bail!("Front matter must be a YAML mapping"),
+    };
+
+    let from_version = read_version(&mapping)?;
+    if from_version > CURRENT_SCHEMA_VERSION {
+        bail!(
+            "Front matter uses schema version {from_version} but this kb-claude supports up to {CURRENT_SCHEMA_VERSION}; upgrade the CLI"
+        );
+    }
+
+    let mut changes = Vec::new();
+    let mut version = from_version;
+    while version < CURRENT_SCHEMA_VERSION {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|migration| migration.from == version)
+            .ok_or_else(|| anyhow!("No migration registered from schema version {version}"))?;
+        changes.extend((migration.apply)(&mut mapping));
+        version += 1;
+    }
+    mapping.insert(
+        Value::from(SCHEMA_VERSION_KEY),
+        Value::from(u64::from(version)),
+    );
+
+    let front_matter: DocumentFrontMatter = serde_yaml::from_value(Value::Mapping(mapping))
+        .with_context(|| "Migrated front matter does not match the current schema")?;
+
+    Ok(MigrationOutcome {
+        from_version,
+        to_version: version,
+        changes,
+        document: Document::new(front_matter, body),
+    })
+}
+
+fn read_version(mapping: &Mapping) -> Result<u32> {
+    match mapping.get(SCHEMA_VERSION_KEY) {
+        None | Some(Value::Null) => Ok(LEGACY_SCHEMA_VERSION),
+        Some(value) => value
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| anyhow!("`{SCHEMA_VERSION_KEY}` must be a positive integer")),
+    }
+}
+
+fn migrate_v1_to_v2(mapping: &mut Mapping) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    if let Some(relations) = mapping.get_mut("ontological_relations") {
+        let converted = type_relations(relations);
+        if converted > 0 {
+            changes.push(format!(
+                "converted {converted} string relation(s) to typed relations"
+            ));
+        }
+    }
+
+    let has_updated = mapping
+        .get("updated_at")
+        .is_some_and(|value| !value.is_null());
+    if !has_updated {
+        if let Some(created) = mapping.get("created_at").cloned() {
+            mapping.insert(Value::from("updated_at"), created);
+            changes.push("added `updated_at` from `created_at`".to_string());
+        }
+    }
+
+    changes
+}
+
+fn type_relations(relations: &mut Value) -> usize {
+    if let Value::String(link) = relations {
+        *relations = Value::Sequence(vec![relation_value(link)]);
+        return 1;
+    }
+
+    let Value::Sequence(items) = relations else {
+        return 0;
+    };
+
+    let mut converted = 0;
+    for item in items.iter_mut() {
+        if matches!(item, Value::Mapping(_)) {
+            continue;
+        }
+        if let Some(link) = relation_target_from_yaml(item) {
+            *item = relation_value(&link);
+            converted += 1;
+        }
+    }
+    converted
+}
+
+fn relation_value(link: &str) -> Value {
+    let mut relation = Mapping::new();
+    relation.insert(
+        Value::from("relates_to"),
+        Value::from(normalize_relation_target(link)),
+    );
+    Value::Mapping(relation)
+}
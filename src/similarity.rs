@@ -0,0 +1,153 @@
+use std::collections::{HashMap, HashSet};
+
+pub const DUPLICATE_THRESHOLD: f64 = 0.75;
+
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "after", "all", "also", "an", "and", "any", "are", "as", "at", "be", "been",
+    "before", "but", "by", "can", "do", "does", "for", "from", "has", "have", "how", "if", "in",
+    "into", "is", "it", "its", "not", "of", "on", "or", "so", "than", "that", "the", "then",
+    "there", "these", "this", "to", "was", "we", "were", "what", "when", "which", "while", "who",
+    "why", "will", "with", "you",
+];
+
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| word.chars().count() > 1)
+        .map(str::to_lowercase)
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .map(|word| match word.strip_suffix('s') {
+            Some(stem) if stem.len() > 2 && !stem.ends_with(['s', 'u', 'i']) => stem.to_string(),
+            _ => word,
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TfIdf {
+    vectors: Vec<HashMap<String, f64>>,
+}
+
+impl TfIdf {
+    pub fn new<S: AsRef<str>>(texts: &[S]) -> Self {
+        let token_counts: Vec<HashMap<String, usize>> = texts
+            .iter()
+            .map(|text| {
+                let mut counts = HashMap::new();
+                for token in tokenize(text.as_ref()) {
+                    *counts.entry(token).or_insert(0) += 1;
+                }
+                counts
+            })
+            .collect();
+
+        let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+        for counts in &token_counts {
+            for token in counts.keys() {
+                *document_frequency.entry(token).or_insert(0) += 1;
+            }
+        }
+
+        let total = token_counts.len() as f64;
+        let vectors = token_counts
+            .iter()
+            .map(|counts| {
+                let length: usize = counts.values().sum();
+                let mut vector: HashMap<String, f64> = counts
+                    .iter()
+                    .map(|(token, count)| {
+                        let tf = *count as f64 / length as f64;
+                        let idf = ((total + 1.0)
+                            / (document_frequency[token.as_str()] as f64 + 1.0))
+                            .ln()
+                            + 1.0;
+                        (token.clone(), tf * idf)
+                    })
+                    .collect();
+                let norm = vector
+                    .values()
+                    .map(|weight| weight * weight)
+                    .sum::<f64>()
+                    .sqrt();
+                if norm > 0.0 {
+                    vector.values_mut().for_each(|weight| *weight /= norm);
+                }
+                vector
+            })
+            .collect();
+
+        Self { vectors }
+    }
+
+    pub fn similarity(&self, left: usize, right: usize) -> f64 {
+        let (Some(left), Some(right)) = (self.vectors.get(left), self.vectors.get(right)) else {
+            return 0.0;
+        };
+        let (small, large) = if left.len() <= right.len() {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        small
+            .iter()
+            .filter_map(|(token, weight)| large.get(token).map(|other| weight * other))
+            .sum()
+    }
+}
+
+pub fn normalized_edit_distance(left: &str, right: &str) -> f64 {
+    let left: Vec<char> = left.chars().collect();
+    let right: Vec<char> = right.chars().collect();
+    let longest = left.len().max(right.len());
+    if longest == 0 {
+        return 0.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+    for (row, left_char) in left.iter().enumerate() {
+        let mut current = vec![row + 1];
+        for (column, right_char) in right.iter().enumerate() {
+            let substitution = previous[column] + usize::from(left_char != right_char);
+            current.push(
+                substitution
+                    .min(previous[column + 1] + 1)
+                    .min(current[column] + 1),
+            );
+        }
+        previous = current;
+    }
+
+    previous[right.len()] as f64 / longest as f64
+}
+
+pub fn token_overlap(left: &str, right: &str) -> f64 {
+    let left: HashSet<String> = tokenize(left).into_iter().collect();
+    let right: HashSet<String> = tokenize(right).into_iter().collect();
+    let union = left.union(&right).count();
+    if union == 0 {
+        return 0.0;
+    }
+    left.intersection(&right).count() as f64 / union as f64
+}
+
+/// How alike two documents look by title and link: the better of slug edit similarity and
+/// title token overlap. Slugs that differ in their numbers (`api-v1-migration` and
+/// `api-v2-migration`) name different things, so only their titles are compared.
+pub fn title_similarity(
+    left_title: &str,
+    left_link: &str,
+    right_title: &str,
+    right_link: &str,
+) -> f64 {
+    let overlap = token_overlap(left_title, right_title);
+    if numbers(left_link) != numbers(right_link) {
+        return overlap;
+    }
+    let slug_similarity = 1.0 - normalized_edit_distance(left_link, right_link);
+    slug_similarity.max(overlap)
+}
+
+fn numbers(text: &str) -> Vec<&str> {
+    text.split(|ch: char| !ch.is_ascii_digit())
+        .filter(|run| !run.is_empty())
+        .collect()
+}
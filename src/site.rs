@@ -0,0 +1,808 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::f64::consts::PI;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag, TagEnd};
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::fs::{is_ignored_path, DocumentEntry, CLAUDE_DIRECTORIES, MD_EXTENSION};
+use crate::graph::{EdgeKind, KbGraph};
+use crate::markdown::{
+    apply_edits, escape_html, escape_link_text, heading_anchor, is_external_destination,
+    percent_decode, percent_encode_path, strip_marked_section, summarize, wikilinks,
+    BACKLINKS_SECTION,
+};
+use crate::model::{slugify, SlugPolicy};
+
+const STYLESHEET: &str = "style.css";
+const SEARCH_INDEX: &str = "search-index.js";
+const SEARCH_SCRIPT: &str = "search.js";
+const TAG_INDEX: &str = "tags/index.html";
+const SUMMARY_LENGTH: usize = 200;
+const SEARCH_TEXT_LENGTH: usize = 5000;
+const MARKDOWN_OPTIONS: Options = Options::ENABLE_TABLES
+    .union(Options::ENABLE_STRIKETHROUGH)
+    .union(Options::ENABLE_TASKLISTS);
+
+#[derive(Debug, Clone, Default)]
+pub struct Site {
+    pages: BTreeMap<String, String>,
+}
+
+impl Site {
+    /// Listing pages keep their fixed paths; a document or tag whose page would land on an
+    /// already claimed path gets a `-2`, `-3`, … suffix instead of replacing it.
+    pub fn build(
+        claude_root: &Path,
+        entries: &[DocumentEntry],
+        timezone: Tz,
+        slug_policy: SlugPolicy,
+    ) -> Self {
+        let graph = KbGraph::from_entries(entries, slug_policy);
+        let mut taken: HashSet<String> = [
+            STYLESHEET,
+            SEARCH_SCRIPT,
+            SEARCH_INDEX,
+            "index.html",
+            "search.html",
+            "graph.html",
+            TAG_INDEX,
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        taken.extend(
+            CLAUDE_DIRECTORIES
+                .iter()
+                .map(|doc_type| type_index_url(doc_type)),
+        );
+
+        let mut documents: Vec<SiteDocument> = entries
+            .iter()
+            .map(|entry| SiteDocument::new(entry, claude_root, timezone, &mut taken))
+            .collect();
+        documents.sort_by(|left, right| {
+            left.title
+                .to_lowercase()
+                .cmp(&right.title.to_lowercase())
+                .then_with(|| left.link.cmp(&right.link))
+                .then_with(|| left.url.cmp(&right.url))
+        });
+        let routes = Routes::new(&documents, &mut taken);
+
+        let mut pages = BTreeMap::new();
+        pages.insert(STYLESHEET.to_string(), STYLE.to_string());
+        pages.insert(SEARCH_SCRIPT.to_string(), SEARCH_JS.to_string());
+        pages.insert(SEARCH_INDEX.to_string(), render_search_index(&documents));
+        pages.insert("index.html".to_string(), render_home(&documents));
+        pages.insert("search.html".to_string(), render_search_page());
+        pages.insert("graph.html".to_string(), render_graph_page(&graph, &routes));
+        pages.extend(render_tag_pages(&documents, &routes));
+
+        for (doc_type, members) in group_by_type(&documents) {
+            pages.insert(
+                type_index_url(&doc_type),
+                render_type_page(&doc_type, &members),
+            );
+        }
+
+        for document in &documents {
+            pages.insert(
+                document.url.clone(),
+                render_document(document, &graph, &routes),
+            );
+        }
+
+        Self { pages }
+    }
+
+    pub fn pages(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pages
+            .iter()
+            .map(|(path, content)| (path.as_str(), content.as_str()))
+    }
+
+    pub fn page(&self, path: &str) -> Option<&str> {
+        self.pages.get(path).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+}
+
+pub fn asset_files(claude_root: &Path) -> Vec<(String, PathBuf)> {
+    WalkDir::new(claude_root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| !is_ignored_path(path, claude_root))
+        .filter(|path| path.extension().is_none_or(|ext| ext != MD_EXTENSION))
+        .filter_map(|path| {
+            let relative = path.strip_prefix(claude_root).ok()?;
+            relative.parent()?.file_name()?;
+            let site_path = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            Some((site_path, path))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+struct SiteDocument {
+    title: String,
+    link: String,
+    doc_type: String,
+    uuid: String,
+    tags: Vec<String>,
+    relations: Vec<String>,
+    summary: Option<String>,
+    created: String,
+    updated: String,
+    body: String,
+    source: String,
+    url: String,
+}
+
+impl SiteDocument {
+    fn new(
+        entry: &DocumentEntry,
+        claude_root: &Path,
+        timezone: Tz,
+        taken: &mut HashSet<String>,
+    ) -> Self {
+        let front = &entry.document.front_matter;
+        let body = strip_marked_section(&entry.document.body, BACKLINKS_SECTION);
+        let stem = entry
+            .path
+            .strip_prefix(claude_root)
+            .unwrap_or(&entry.path)
+            .with_extension("")
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let date = |value: DateTime<Utc>| value.with_timezone(&timezone).date_naive().to_string();
+
+        Self {
+            title: front.title.clone(),
+            link: front.link.clone(),
+            doc_type: front.doc_type.clone(),
+            uuid: front.uuid.hyphenated().to_string(),
+            tags: front.tags.clone(),
+            relations: front
+                .ontological_relations
+                .iter()
+                .map(|relation| relation.relates_to.clone())
+                .collect(),
+            summary: front
+                .summary
+                .clone()
+                .or_else(|| summarize(&body, SUMMARY_LENGTH)),
+            created: date(front.created_at),
+            updated: date(front.last_updated()),
+            source: format!("{stem}.{MD_EXTENSION}"),
+            url: claim_page(taken, &stem),
+            body,
+        }
+    }
+}
+
+fn group_by_type(documents: &[SiteDocument]) -> Vec<(String, Vec<&SiteDocument>)> {
+    let mut by_type: BTreeMap<&str, Vec<&SiteDocument>> = BTreeMap::new();
+    for document in documents {
+        by_type
+            .entry(document.doc_type.as_str())
+            .or_default()
+            .push(document);
+    }
+
+    // Only known types get a listing page; `type` is free text and must not pick a path.
+    CLAUDE_DIRECTORIES
+        .iter()
+        .filter_map(|doc_type| Some((doc_type.to_string(), by_type.remove(doc_type)?)))
+        .collect()
+}
+
+struct Routes<'a> {
+    by_link: HashMap<&'a str, &'a SiteDocument>,
+    by_source: HashMap<&'a str, &'a SiteDocument>,
+    tags: BTreeMap<&'a str, String>,
+}
+
+impl<'a> Routes<'a> {
+    fn new(documents: &'a [SiteDocument], taken: &mut HashSet<String>) -> Self {
+        let tags: BTreeSet<&str> = documents
+            .iter()
+            .flat_map(|document| document.tags.iter().map(String::as_str))
+            .collect();
+
+        Self {
+            by_link: documents
+                .iter()
+                .map(|document| (document.link.as_str(), document))
+                .collect(),
+            by_source: documents
+                .iter()
+                .map(|document| (document.source.as_str(), document))
+                .collect(),
+            tags: tags
+                .into_iter()
+                .map(|tag| {
+                    let slug = slugify(tag);
+                    let slug = if slug.is_empty() { "tag" } else { &slug };
+                    (tag, claim_page(taken, &format!("tags/{slug}")))
+                })
+                .collect(),
+        }
+    }
+
+    fn tag(&self, tag: &str) -> &str {
+        self.tags.get(tag).map_or(TAG_INDEX, String::as_str)
+    }
+}
+
+fn claim_page(taken: &mut HashSet<String>, stem: &str) -> String {
+    let mut path = format!("{stem}.html");
+    let mut counter = 2;
+    while !taken.insert(path.clone()) {
+        path = format!("{stem}-{counter}.html");
+        counter += 1;
+    }
+    path
+}
+
+fn type_index_url(doc_type: &str) -> String {
+    format!("{doc_type}/index.html")
+}
+
+fn layout(title: &str, root: &str, content: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n\
+         <link rel=\"stylesheet\" href=\"{root}{STYLESHEET}\">\n\
+         </head>\n\
+         <body>\n\
+         <nav><a href=\"{root}index.html\">Home</a> <a href=\"{root}{TAG_INDEX}\">Tags</a> \
+         <a href=\"{root}graph.html\">Graph</a> <a href=\"{root}search.html\">Search</a></nav>\n\
+         <main>\n\
+         {content}\
+         </main>\n\
+         </body>\n\
+         </html>\n",
+        title = escape_html(title),
+    )
+}
+
+fn document_list(documents: &[&SiteDocument], root: &str) -> String {
+    if documents.is_empty() {
+        return "<p>No documents.</p>\n".to_string();
+    }
+
+    let mut html = String::from("<ul class=\"documents\">\n");
+    for document in documents {
+        html.push_str(&format!(
+            "<li><a href=\"{root}{}\">{}</a> <span class=\"meta\">{} · {}</span>",
+            escape_html(&percent_encode_path(&document.url)),
+            escape_html(&document.title),
+            escape_html(&document.doc_type),
+            document.updated
+        ));
+        if let Some(summary) = &document.summary {
+            html.push_str(&format!(
+                "<br><span class=\"summary\">{}</span>",
+                escape_html(summary)
+            ));
+        }
+        html.push_str("</li>\n");
+    }
+    html.push_str("</ul>\n");
+    html
+}
+
+fn render_home(documents: &[SiteDocument]) -> String {
+    let mut content = format!(
+        "<h1>Knowledge Base</h1>\n<p>{} document(s).</p>\n<h2>Types</h2>\n<ul>\n",
+        documents.len()
+    );
+    for (doc_type, members) in group_by_type(documents) {
+        content.push_str(&format!(
+            "<li><a href=\"{}\">{}</a> ({})</li>\n",
+            escape_html(&percent_encode_path(&type_index_url(&doc_type))),
+            escape_html(&doc_type),
+            members.len()
+        ));
+    }
+    content.push_str("</ul>\n<h2>Recently updated</h2>\n");
+
+    let mut recent: Vec<&SiteDocument> = documents.iter().collect();
+    recent.sort_by(|left, right| right.updated.cmp(&left.updated));
+    recent.truncate(10);
+    content.push_str(&document_list(&recent, ""));
+
+    layout("Knowledge Base", "", &content)
+}
+
+fn render_type_page(doc_type: &str, members: &[&SiteDocument]) -> String {
+    let content = format!(
+        "<h1>{} ({})</h1>\n{}",
+        escape_html(doc_type),
+        members.len(),
+        document_list(members, "../")
+    );
+    layout(doc_type, "../", &content)
+}
+
+fn render_tag_pages(documents: &[SiteDocument], routes: &Routes) -> BTreeMap<String, String> {
+    let mut by_tag: BTreeMap<&str, Vec<&SiteDocument>> = BTreeMap::new();
+    for document in documents {
+        for tag in &document.tags {
+            by_tag.entry(tag.as_str()).or_default().push(document);
+        }
+    }
+
+    let mut pages = BTreeMap::new();
+    let mut index = String::from("<h1>Tags</h1>\n<ul>\n");
+    for (tag, members) in &by_tag {
+        let url = routes.tag(tag);
+        index.push_str(&format!(
+            "<li><a href=\"../{}\">{}</a> ({})</li>\n",
+            escape_html(&percent_encode_path(url)),
+            escape_html(tag),
+            members.len()
+        ));
+        let content = format!(
+            "<h1>Tag: {} ({})</h1>\n{}",
+            escape_html(tag),
+            members.len(),
+            document_list(members, "../")
+        );
+        pages.insert(
+            url.to_string(),
+            layout(&format!("Tag: {tag}"), "../", &content),
+        );
+    }
+    index.push_str("</ul>\n");
+    pages.insert(TAG_INDEX.to_string(), layout("Tags", "../", &index));
+    pages
+}
+
+fn render_document(document: &SiteDocument, graph: &KbGraph, routes: &Routes) -> String {
+    let root = "../".repeat(document.url.matches('/').count());
+    let root = root.as_str();
+    let href =
+        |target: &SiteDocument| format!("{root}{}", escape_html(&percent_encode_path(&target.url)));
+
+    let mut content = format!(
+        "<h1>{}</h1>\n<dl class=\"front-matter\">\n",
+        escape_html(&document.title)
+    );
+    let doc_type = if CLAUDE_DIRECTORIES.contains(&document.doc_type.as_str()) {
+        format!(
+            "<a href=\"{root}{}\">{}</a>",
+            escape_html(&percent_encode_path(&type_index_url(&document.doc_type))),
+            escape_html(&document.doc_type)
+        )
+    } else {
+        escape_html(&document.doc_type)
+    };
+    content.push_str(&format!("<dt>Type</dt><dd>{doc_type}</dd>\n"));
+    if !document.tags.is_empty() {
+        let tags = document
+            .tags
+            .iter()
+            .map(|tag| {
+                format!(
+                    "<a href=\"{root}{}\">{}</a>",
+                    escape_html(&percent_encode_path(routes.tag(tag))),
+                    escape_html(tag)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        content.push_str(&format!("<dt>Tags</dt><dd>{tags}</dd>\n"));
+    }
+    if !document.relations.is_empty() {
+        let relations = document
+            .relations
+            .iter()
+            .map(|relation| {
+                match graph
+                    .resolve(relation)
+                    .and_then(|link| routes.by_link.get(link))
+                {
+                    Some(target) => format!(
+                        "<a href=\"{}\">{}</a>",
+                        href(target),
+                        escape_html(&target.title)
+                    ),
+                    None => escape_html(relation),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        content.push_str(&format!("<dt>Relations</dt><dd>{relations}</dd>\n"));
+    }
+    content.push_str(&format!(
+        "<dt>Created</dt><dd>{}</dd>\n<dt>Updated</dt><dd>{}</dd>\n<dt>UUID</dt><dd><code>{}</code></dd>\n</dl>\n",
+        document.created, document.updated, document.uuid
+    ));
+
+    content.push_str("<article>\n");
+    content.push_str(&render_body(document, root, graph, routes));
+    content.push_str("</article>\n");
+
+    let mut referrers: Vec<&SiteDocument> = graph
+        .incoming(&document.link)
+        .filter_map(|link| routes.by_link.get(link).copied())
+        .collect();
+    referrers.sort_by(|left, right| {
+        left.title
+            .cmp(&right.title)
+            .then_with(|| left.link.cmp(&right.link))
+    });
+    referrers.dedup_by(|left, right| left.link == right.link);
+    if !referrers.is_empty() {
+        content.push_str("<section class=\"backlinks\">\n<h2>Referenced by</h2>\n<ul>\n");
+        for referrer in referrers {
+            content.push_str(&format!(
+                "<li><a href=\"{}\">{}</a></li>\n",
+                href(referrer),
+                escape_html(&referrer.title)
+            ));
+        }
+        content.push_str("</ul>\n</section>\n");
+    }
+
+    layout(&document.title, root, &content)
+}
+
+/// Renders a Markdown body to HTML: wikilinks become links to their pages, relative `.md`
+/// links point at the matching `.html` page, headings get GitHub-style ids, and raw HTML is
+/// shown as text instead of being passed through.
+fn render_body(document: &SiteDocument, root: &str, graph: &KbGraph, routes: &Routes) -> String {
+    let body = document.body.as_str();
+    let edits = wikilinks(body)
+        .into_iter()
+        .filter_map(|wikilink| {
+            let target = if wikilink.target.is_empty() {
+                None
+            } else {
+                Some(routes.by_link.get(graph.resolve(&wikilink.target)?)?)
+            };
+            let mut destination = match target {
+                Some(target) => format!("{root}{}", percent_encode_path(&target.url)),
+                None => String::new(),
+            };
+            if let Some(heading) = &wikilink.heading {
+                destination.push('#');
+                destination.push_str(&heading_anchor(heading));
+            }
+            let label = wikilink.label.clone().unwrap_or_else(|| {
+                let reference = &body[wikilink.span.clone()];
+                reference
+                    .split('|')
+                    .next()
+                    .unwrap_or(reference)
+                    .trim()
+                    .to_string()
+            });
+            let span = wikilink.span.start - 2..wikilink.span.end + 2;
+            Some((
+                span,
+                format!("[{}](<{destination}>)", escape_link_text(&label)),
+            ))
+        })
+        .collect();
+    let body = apply_edits(body, edits);
+
+    let mut events = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut heading: Option<(usize, Vec<Event>)> = None;
+
+    for event in Parser::new_ext(&body, MARKDOWN_OPTIONS) {
+        match event {
+            Event::Start(Tag::Heading { .. }) => {
+                heading = Some((events.len(), Vec::new()));
+                events.push(event);
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((start, inner)) = heading.take() {
+                    let text: String = inner
+                        .iter()
+                        .filter_map(|event| match event {
+                            Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+                            _ => None,
+                        })
+                        .collect();
+                    let base = heading_anchor(&text);
+                    let count = seen.entry(base.clone()).or_insert(0);
+                    let anchor = if *count == 0 {
+                        base
+                    } else {
+                        format!("{base}-{count}")
+                    };
+                    *count += 1;
+                    if let Event::Start(Tag::Heading { id, .. }) = &mut events[start] {
+                        *id = Some(CowStr::from(anchor));
+                    }
+                    events.extend(inner);
+                }
+                events.push(event);
+            }
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => {
+                let event = Event::Start(Tag::Link {
+                    link_type,
+                    dest_url: CowStr::from(page_destination(&dest_url, document, root, routes)),
+                    title,
+                    id,
+                });
+                match &mut heading {
+                    Some((_, inner)) => inner.push(event),
+                    None => events.push(event),
+                }
+            }
+            event => {
+                let event = match event {
+                    Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+                    event => event,
+                };
+                match &mut heading {
+                    Some((_, inner)) => inner.push(event),
+                    None => events.push(event),
+                }
+            }
+        }
+    }
+
+    let mut html = String::new();
+    html::push_html(&mut html, events.into_iter());
+    html
+}
+
+fn page_destination(destination: &str, from: &SiteDocument, root: &str, routes: &Routes) -> String {
+    if is_external_destination(destination) || destination.starts_with(['#', '/']) {
+        return destination.to_string();
+    }
+    let split = destination.find(['#', '?']).unwrap_or(destination.len());
+    let (path, suffix) = destination.split_at(split);
+    let Some(stem) = path.strip_suffix(".md") else {
+        return destination.to_string();
+    };
+
+    let mut segments: Vec<&str> = from.source.split('/').collect();
+    segments.pop();
+    let decoded = percent_decode(path);
+    for segment in decoded.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    match routes.by_source.get(segments.join("/").as_str()) {
+        Some(target) => format!("{root}{}{suffix}", percent_encode_path(&target.url)),
+        None => format!("{stem}.html{suffix}"),
+    }
+}
+
+fn render_graph_page(graph: &KbGraph, routes: &Routes) -> String {
+    let nodes: Vec<&str> = graph.nodes().map(|node| node.link.as_str()).collect();
+    let size = 800.0;
+    let center = size / 2.0;
+    let radius = center - 120.0;
+    let positions: HashMap<&str, (f64, f64)> = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, link)| {
+            let angle = 2.0 * PI * index as f64 / nodes.len().max(1) as f64 - PI / 2.0;
+            (
+                *link,
+                (center + radius * angle.cos(), center + radius * angle.sin()),
+            )
+        })
+        .collect();
+
+    let mut svg = format!(
+        "<svg class=\"graph\" xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {size} {size}\">\n"
+    );
+    for edge in graph.edges() {
+        let (Some((x1, y1)), Some((x2, y2))) = (
+            positions.get(edge.source.as_str()),
+            positions.get(edge.target.as_str()),
+        ) else {
+            continue;
+        };
+        let dash = if edge.kind == EdgeKind::Wikilink {
+            " stroke-dasharray=\"4 3\""
+        } else {
+            ""
+        };
+        svg.push_str(&format!(
+            "<line x1=\"{x1:.1}\" y1=\"{y1:.1}\" x2=\"{x2:.1}\" y2=\"{y2:.1}\"{dash}/>\n"
+        ));
+    }
+    for link in &nodes {
+        let (x, y) = positions[link];
+        let Some(document) = routes.by_link.get(link) else {
+            continue;
+        };
+        svg.push_str(&format!(
+            "<a href=\"{}\"><circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"6\"><title>{}</title></circle>\
+             <text x=\"{:.1}\" y=\"{:.1}\">{}</text></a>\n",
+            escape_html(&percent_encode_path(&document.url)),
+            escape_html(&document.title),
+            x + 9.0,
+            y + 4.0,
+            escape_html(link)
+        ));
+    }
+    svg.push_str("</svg>\n");
+
+    let mut content = format!(
+        "<h1>Relation graph</h1>\n<p>{} document(s), {} relation(s). Dashed lines are wikilinks.</p>\n{svg}",
+        nodes.len(),
+        graph.edges().count()
+    );
+    content.push_str("<h2>Relations</h2>\n<ul>\n");
+    for edge in graph.edges() {
+        let (Some(source), Some(target)) = (
+            routes.by_link.get(edge.source.as_str()),
+            routes.by_link.get(edge.target.as_str()),
+        ) else {
+            continue;
+        };
+        content.push_str(&format!(
+            "<li><a href=\"{}\">{}</a> → <a href=\"{}\">{}</a></li>\n",
+            escape_html(&percent_encode_path(&source.url)),
+            escape_html(&source.title),
+            escape_html(&percent_encode_path(&target.url)),
+            escape_html(&target.title)
+        ));
+    }
+    content.push_str("</ul>\n");
+
+    layout("Relation graph", "", &content)
+}
+
+#[derive(Serialize)]
+struct SearchRecord<'a> {
+    title: &'a str,
+    link: &'a str,
+    #[serde(rename = "type")]
+    doc_type: &'a str,
+    tags: &'a [String],
+    summary: Option<&'a str>,
+    url: &'a str,
+    text: String,
+}
+
+/// The search index as a script assigning `window.KB_SEARCH_INDEX`, which loads from
+/// `file://` pages where `fetch` of a JSON file would be blocked.
+fn render_search_index(documents: &[SiteDocument]) -> String {
+    let records: Vec<SearchRecord> = documents
+        .iter()
+        .map(|document| SearchRecord {
+            title: &document.title,
+            link: &document.link,
+            doc_type: &document.doc_type,
+            tags: &document.tags,
+            summary: document.summary.as_deref(),
+            url: &document.url,
+            text: plain_text(&document.body),
+        })
+        .collect();
+    let json = serde_json::to_string(&records).unwrap_or_else(|_| "[]".to_string());
+    format!("window.KB_SEARCH_INDEX = {json};\n")
+}
+
+fn plain_text(body: &str) -> String {
+    let text = Parser::new_ext(body, MARKDOWN_OPTIONS)
+        .filter_map(|event| match event {
+            Event::Text(text) | Event::Code(text) => Some(text.to_string()),
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => Some(" ".to_string()),
+            _ => None,
+        })
+        .collect::<String>();
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(SEARCH_TEXT_LENGTH)
+        .collect()
+}
+
+fn render_search_page() -> String {
+    let content = format!(
+        "<h1>Search</h1>\n\
+         <input id=\"search\" type=\"search\" placeholder=\"Search titles, tags and text\" autofocus>\n\
+         <ul id=\"results\" class=\"documents\"></ul>\n\
+         <script src=\"{SEARCH_INDEX}\"></script>\n\
+         <script src=\"{SEARCH_SCRIPT}\"></script>\n"
+    );
+    layout("Search", "", &content)
+}
+
+const STYLE: &str = "body { font-family: system-ui, sans-serif; margin: 0; color: #222; }
+nav { padding: 0.75rem 1.5rem; background: #f3f3f3; border-bottom: 1px solid #ddd; }
+nav a { margin-right: 1rem; }
+main { max-width: 60rem; margin: 0 auto; padding: 1rem 1.5rem 3rem; }
+a { color: #0b5cad; }
+.meta, .summary { color: #666; font-size: 0.9em; }
+.documents li { margin-bottom: 0.5rem; }
+.front-matter { display: grid; grid-template-columns: max-content 1fr; gap: 0.25rem 1rem; }
+.front-matter dt { font-weight: 600; }
+.front-matter dd { margin: 0; }
+pre { background: #f6f8fa; padding: 0.75rem; overflow-x: auto; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ddd; padding: 0.25rem 0.5rem; }
+.graph { width: 100%; max-width: 800px; }
+.graph line { stroke: #999; }
+.graph circle { fill: #0b5cad; }
+.graph text { font-size: 11px; }
+#search { width: 100%; padding: 0.5rem; font-size: 1rem; }
+";
+
+const SEARCH_JS: &str = "(function () {
+  var input = document.getElementById('search');
+  var results = document.getElementById('results');
+  var index = window.KB_SEARCH_INDEX || [];
+
+  function escape(value) {
+    return String(value).replace(/[&<>\"']/g, function (ch) {
+      return { '&': '&amp;', '<': '&lt;', '>': '&gt;', '\"': '&quot;', \"'\": '&#39;' }[ch];
+    });
+  }
+
+  function search() {
+    var terms = input.value.toLowerCase().split(/\\s+/).filter(Boolean);
+    results.innerHTML = '';
+    if (!terms.length) { return; }
+    index
+      .map(function (doc) {
+        var title = doc.title.toLowerCase();
+        var haystack = [title, doc.link, doc.type, doc.tags.join(' '), doc.summary || '', doc.text]
+          .join(' ').toLowerCase();
+        if (!terms.every(function (term) { return haystack.indexOf(term) !== -1; })) { return null; }
+        var score = terms.filter(function (term) { return title.indexOf(term) !== -1; }).length;
+        return { doc: doc, score: score };
+      })
+      .filter(Boolean)
+      .sort(function (a, b) { return b.score - a.score || a.doc.title.localeCompare(b.doc.title); })
+      .forEach(function (hit) {
+        var item = document.createElement('li');
+        item.innerHTML = '<a href=\"' + escape(hit.doc.url) + '\">' + escape(hit.doc.title) + '</a> '
+          + '<span class=\"meta\">' + escape(hit.doc.type) + '</span>'
+          + (hit.doc.summary ? '<br><span class=\"summary\">' + escape(hit.doc.summary) + '</span>' : '');
+        results.appendChild(item);
+      });
+  }
+
+  input.addEventListener('input', search);
+  var query = new URLSearchParams(window.location.search).get('q');
+  if (query) { input.value = query; search(); }
+})();
+";
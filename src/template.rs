@@ -0,0 +1,265 @@
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+use crate::markdown::escape_table_cell;
+
+/// A parsed template using a small Handlebars-like syntax:
+///
+/// - `{{ name }}` or `{{ entry.name }}` inserts a value; `{{ name | cell }}` escapes it for a
+///   Markdown table cell.
+/// - `{{#each list}} … {{/each}}` repeats its body with each item in scope.
+/// - `{{#if name}} … {{else}} … {{/if}}` renders a branch depending on whether the value is
+///   present and non-empty.
+///
+/// Block tags alone on a line consume that line, so they do not leave blank rows in tables.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Value {
+        path: String,
+        filter: Option<Filter>,
+    },
+    Each {
+        path: String,
+        body: Vec<Node>,
+    },
+    If {
+        path: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Filter {
+    Cell,
+}
+
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Tag(String),
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut position = 0;
+        let (nodes, closing) = parse_nodes(&tokens, &mut position)?;
+        if let Some(closing) = closing {
+            bail!("Unexpected `{{{{{closing}}}}}` in template");
+        }
+        Ok(Self { nodes })
+    }
+
+    pub fn render(&self, context: &Value) -> String {
+        let mut output = String::new();
+        render_nodes(&self.nodes, &mut vec![context], &mut output);
+        output
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            bail!("Unclosed `{{{{` in template");
+        };
+        let tag = rest[start + 2..start + 2 + length].trim().to_string();
+        let mut text = &rest[..start];
+        let mut after = &rest[start + 2 + length + 2..];
+
+        if is_block_tag(&tag) {
+            let line_start = text.rfind('\n').map_or(0, |index| index + 1);
+            let line_end = after.find('\n');
+            let alone_before = text[line_start..].trim().is_empty()
+                && (line_start > 0 || tokens.is_empty() || ends_with_newline(&tokens));
+            let alone_after = after[..line_end.unwrap_or(after.len())].trim().is_empty();
+            if alone_before && alone_after {
+                text = &text[..line_start];
+                after = &after[line_end.map_or(after.len(), |end| end + 1)..];
+            }
+        }
+
+        if !text.is_empty() {
+            tokens.push(Token::Text(text.to_string()));
+        }
+        tokens.push(Token::Tag(tag));
+        rest = after;
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+    Ok(tokens)
+}
+
+fn is_block_tag(tag: &str) -> bool {
+    tag.starts_with('#') || tag.starts_with('/') || tag == "else"
+}
+
+fn ends_with_newline(tokens: &[Token]) -> bool {
+    match tokens.last() {
+        Some(Token::Text(text)) => text.ends_with('\n'),
+        Some(Token::Tag(tag)) => is_block_tag(tag),
+        None => true,
+    }
+}
+
+fn parse_nodes(tokens: &[Token], position: &mut usize) -> Result<(Vec<Node>, Option<String>)> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = tokens.get(*position) {
+        *position += 1;
+        let tag = match token {
+            Token::Text(text) => {
+                nodes.push(Node::Text(text.clone()));
+                continue;
+            }
+            Token::Tag(tag) => tag.as_str(),
+        };
+
+        if tag.starts_with('/') || tag == "else" {
+            return Ok((nodes, Some(tag.to_string())));
+        }
+
+        if let Some(block) = tag.strip_prefix('#') {
+            let (keyword, path) = block
+                .split_once(char::is_whitespace)
+                .map(|(keyword, path)| (keyword, path.trim().to_string()))
+                .unwrap_or((block, String::new()));
+            if path.is_empty() {
+                bail!("`{{{{#{keyword}}}}}` needs a value to test or iterate");
+            }
+
+            let (body, closing) = parse_nodes(tokens, position)?;
+            match keyword {
+                "each" => {
+                    expect_closing(closing.as_deref(), "/each")?;
+                    nodes.push(Node::Each { path, body });
+                }
+                "if" => {
+                    let otherwise = match closing.as_deref() {
+                        Some("else") => {
+                            let (otherwise, closing) = parse_nodes(tokens, position)?;
+                            expect_closing(closing.as_deref(), "/if")?;
+                            otherwise
+                        }
+                        closing => {
+                            expect_closing(closing, "/if")?;
+                            Vec::new()
+                        }
+                    };
+                    nodes.push(Node::If {
+                        path,
+                        then: body,
+                        otherwise,
+                    });
+                }
+                other => bail!("Unknown template block `#{other}`"),
+            }
+            continue;
+        }
+
+        let (path, filter) = match tag.split_once('|') {
+            Some((path, filter)) => match filter.trim() {
+                "cell" => (path.trim(), Some(Filter::Cell)),
+                other => bail!("Unknown template filter `{other}`"),
+            },
+            None => (tag, None),
+        };
+        if path.is_empty() {
+            bail!("Empty `{{{{}}}}` in template");
+        }
+        nodes.push(Node::Value {
+            path: path.to_string(),
+            filter,
+        });
+    }
+
+    Ok((nodes, None))
+}
+
+fn expect_closing(closing: Option<&str>, expected: &str) -> Result<()> {
+    match closing {
+        Some(found) if found == expected => Ok(()),
+        Some(found) => bail!("Expected `{{{{{expected}}}}}` but found `{{{{{found}}}}}`"),
+        None => bail!("Missing `{{{{{expected}}}}}` in template"),
+    }
+}
+
+fn render_nodes<'a>(nodes: &'a [Node], scopes: &mut Vec<&'a Value>, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Value { path, filter } => {
+                let text = lookup(scopes, path).map(display).unwrap_or_default();
+                match filter {
+                    Some(Filter::Cell) => output.push_str(&escape_table_cell(&text)),
+                    None => output.push_str(&text),
+                }
+            }
+            Node::Each { path, body } => {
+                if let Some(Value::Array(items)) = lookup(scopes, path) {
+                    for item in items {
+                        scopes.push(item);
+                        render_nodes(body, scopes, output);
+                        scopes.pop();
+                    }
+                }
+            }
+            Node::If {
+                path,
+                then,
+                otherwise,
+            } => {
+                let branch = if lookup(scopes, path).is_some_and(is_truthy) {
+                    then
+                } else {
+                    otherwise
+                };
+                render_nodes(branch, scopes, output);
+            }
+        }
+    }
+}
+
+fn lookup<'a>(scopes: &[&'a Value], path: &str) -> Option<&'a Value> {
+    if path == "this" {
+        return scopes.last().copied();
+    }
+    let mut segments = path.trim_start_matches("this.").split('.');
+    let first = segments.next()?;
+    let mut value = scopes.iter().rev().find_map(|scope| scope.get(first))?;
+    for segment in segments {
+        value = value.get(segment)?;
+    }
+    Some(value)
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(display).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(flag) => *flag,
+        Value::String(text) => !text.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(fields) => !fields.is_empty(),
+        Value::Number(number) => number.as_f64().is_some_and(|number| number != 0.0),
+    }
+}
@@ -0,0 +1,72 @@
+//! Fixtures shared by the integration tests; each test crate uses a different subset.
+#![allow(dead_code)]
+
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+
+pub fn write_doc(
+    temp: &TempDir,
+    doc_type: &str,
+    link: &str,
+    front_matter: &str,
+    body: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    temp.child(format!(".claude/{doc_type}/{link}.md"))
+        .write_str(&format!(
+            "---\n\
+             link: {link}\n\
+             type: {doc_type}\n\
+             {front_matter}\n\
+             schema_version: 2\n\
+             ---\n\
+             {body}\n"
+        ))?;
+    Ok(())
+}
+
+pub fn write_titled_doc(
+    temp: &TempDir,
+    doc_type: &str,
+    link: &str,
+    title: &str,
+    body: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    write_doc(
+        temp,
+        doc_type,
+        link,
+        &format!(
+            "title: {title}\n\
+             uuid: {}\n\
+             created_at: 2025-10-20T09:00:00Z",
+            uuid::Uuid::new_v4()
+        ),
+        body,
+    )
+}
+
+pub fn kb() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    write_doc(
+        &temp,
+        "patterns",
+        "retry-with-backoff",
+        "title: Retry with backoff\n\
+         tags: [network, resilience]\n\
+         ontological_relations:\n  - relates_to: timeout-storm\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174001\n\
+         created_at: 2025-10-20T09:00:00Z\n\
+         updated_at: 2025-10-22T09:30:00Z",
+        "Retry failed calls with exponential backoff and jitter.",
+    )?;
+    write_doc(
+        &temp,
+        "debug_history",
+        "timeout-storm",
+        "title: \"Timeout storm, part 1\"\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174002\n\
+         created_at: 2025-10-21T12:00:00Z",
+        "Gateway timeouts cascaded across services.",
+    )?;
+    Ok(temp)
+}
@@ -0,0 +1,239 @@
+use assert_cmd::Command;
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+mod common;
+use common::write_doc;
+
+fn kb() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let temp = common::kb()?;
+    write_doc(
+        &temp,
+        "qa",
+        "why-backoff",
+        "title: Why <backoff>?\n\
+         tags: [Auth Flow]\n\
+         ontological_relations:\n  - relates_to: timeout-storm\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174003\n\
+         created_at: 2025-10-22T09:00:00Z",
+        "Backoff keeps retries from piling up.\n\n\
+         ## Usage\n\n\
+         See [[timeout-storm#Root cause|the storm]] and \
+         [the timeline](../debug_history/timeout-storm.md).\n\n\
+         ![diagram](diagram.png)\n\n\
+         <script>alert(1)</script>",
+    )?;
+    temp.child(".claude/qa/diagram.png")
+        .write_binary(b"\x89PNG")?;
+    Ok(temp)
+}
+
+#[test]
+fn export_site_renders_linked_pages() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["export", "site", "site"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Exported 3 document(s) as 16 page(s) and 1 asset(s)",
+        ));
+
+    for page in [
+        "index.html",
+        "search.html",
+        "search-index.js",
+        "graph.html",
+        "style.css",
+        "patterns/index.html",
+        "tags/index.html",
+        "tags/auth-flow.html",
+        "qa/diagram.png",
+    ] {
+        temp.child(format!("site/{page}"))
+            .assert(predicate::path::exists());
+    }
+
+    temp.child("site/qa/why-backoff.html")
+        .assert(predicate::str::contains("<h1>Why &lt;backoff&gt;?</h1>"))
+        .assert(predicate::str::contains(
+            "<a href=\"../tags/auth-flow.html\">Auth Flow</a>",
+        ))
+        .assert(predicate::str::contains(
+            "<a href=\"../debug_history/timeout-storm.html#root-cause\">the storm</a>",
+        ))
+        .assert(predicate::str::contains(
+            "<a href=\"../debug_history/timeout-storm.html\">the timeline</a>",
+        ))
+        .assert(predicate::str::contains("<h2 id=\"usage\">Usage</h2>"))
+        .assert(predicate::str::contains(
+            "&lt;script&gt;alert(1)&lt;/script&gt;",
+        ))
+        .assert(predicate::str::contains("<script>alert").not());
+
+    temp.child("site/debug_history/timeout-storm.html")
+        .assert(predicate::str::contains("<h2>Referenced by</h2>"))
+        .assert(predicate::str::contains(
+            "<a href=\"../qa/why-backoff.html\">Why &lt;backoff&gt;?</a>",
+        ))
+        .assert(predicate::str::contains(
+            "<a href=\"../patterns/retry-with-backoff.html\">Retry with backoff</a>",
+        ));
+
+    temp.child("site/search-index.js")
+        .assert(predicate::str::starts_with("window.KB_SEARCH_INDEX = ["))
+        .assert(predicate::str::contains(
+            "\"url\":\"debug_history/timeout-storm.html\"",
+        ));
+
+    temp.child("site/graph.html")
+        .assert(predicate::str::contains("<svg"))
+        .assert(predicate::str::contains("<line "));
+
+    Ok(())
+}
+
+#[test]
+fn export_site_refuses_to_write_inside_claude() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["export", "site", ".claude/site"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("outside .claude"));
+
+    Ok(())
+}
+
+#[test]
+fn export_site_keeps_colliding_pages_apart() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    write_doc(
+        &temp,
+        "qa",
+        "index",
+        "title: Index questions\n\
+         tags: [c++, index]\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174003\n\
+         created_at: 2025-10-20T09:00:00Z",
+        "How are indexes built?",
+    )?;
+    write_doc(
+        &temp,
+        "qa",
+        "csharp",
+        "title: C sharp questions\n\
+         tags: [c#]\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174004\n\
+         created_at: 2025-10-20T09:00:00Z",
+        "See [the index questions](index.md).",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["export", "site", "site"])
+        .assert()
+        .success();
+
+    temp.child("site/qa/index.html")
+        .assert(predicate::str::contains("<h1>qa (2)</h1>"));
+    temp.child("site/qa/index-2.html")
+        .assert(predicate::str::contains("<h1>Index questions</h1>"))
+        .assert(predicate::str::contains(
+            "<a href=\"../tags/c-2.html\">c++</a>, <a href=\"../tags/index-2.html\">index</a>",
+        ));
+    temp.child("site/qa/csharp.html")
+        .assert(predicate::str::contains(
+            "<a href=\"../tags/c.html\">c#</a>",
+        ))
+        .assert(predicate::str::contains(
+            "<a href=\"../qa/index-2.html\">the index questions</a>",
+        ));
+    temp.child("site/tags/index.html")
+        .assert(predicate::str::contains("<h1>Tags</h1>"));
+    temp.child("site/tags/c.html")
+        .assert(predicate::str::contains("Tag: c#"));
+    temp.child("site/tags/c-2.html")
+        .assert(predicate::str::contains("Tag: c++"));
+    temp.child("site/tags/index-2.html")
+        .assert(predicate::str::contains("Tag: index"));
+
+    Ok(())
+}
+
+#[test]
+fn export_site_keeps_free_text_types_inside_the_output() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+    write_doc(
+        &temp,
+        "qa",
+        "evil",
+        "title: Evil\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174009\n\
+         created_at: 2025-10-20T09:00:00Z",
+        "Escapes?",
+    )?;
+    let evil = temp.child(".claude/qa/evil.md");
+    let raw = std::fs::read_to_string(evil.path())?;
+    evil.write_str(&raw.replace("type: qa", "type: ../../../escaped"))?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["export", "site", "out/site"])
+        .assert()
+        .success();
+
+    temp.child("escaped").assert(predicate::path::missing());
+    temp.child("out/escaped").assert(predicate::path::missing());
+    temp.child("out/site/qa/evil.html")
+        .assert(predicate::str::contains(
+            "<dt>Type</dt><dd>../../../escaped</dd>",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn export_site_mirrors_nested_directories() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+    temp.child(".claude/qa/sub/nested.md").write_str(
+        "---\n\
+         title: Nested\n\
+         link: nested\n\
+         type: qa\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174010\n\
+         created_at: 2025-10-20T09:00:00Z\n\
+         schema_version: 2\n\
+         ---\n\
+         See [[retry-with-backoff]], [the question](../why-backoff.md) and ![diagram](../diagram.png).\n",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["export", "site", "site"])
+        .assert()
+        .success();
+
+    temp.child("site/qa/sub/nested.html")
+        .assert(predicate::str::contains(
+            "<link rel=\"stylesheet\" href=\"../../style.css\">",
+        ))
+        .assert(predicate::str::contains(
+            "<a href=\"../../patterns/retry-with-backoff.html\">retry-with-backoff</a>",
+        ))
+        .assert(predicate::str::contains(
+            "<a href=\"../../qa/why-backoff.html\">the question</a>",
+        ))
+        .assert(predicate::str::contains("src=\"../diagram.png\""));
+    temp.child("site/qa/index.html")
+        .assert(predicate::str::contains(
+            "<a href=\"../qa/sub/nested.html\">Nested</a>",
+        ));
+
+    Ok(())
+}
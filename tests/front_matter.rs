@@ -0,0 +1,327 @@
+use assert_cmd::Command;
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+#[test]
+fn hand_written_timestamps_are_lenient() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude/qa").create_dir_all()?;
+
+    temp.child(".claude/qa/date-only.md").write_str(
+        "---\n\
+         title: Date Only\n\
+         link: date-only\n\
+         type: qa\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174000\n\
+         created_at: 2025-10-23\n\
+         schema_version: 2\n\
+         ---\n\
+         Hand written entry without updated_at.\n",
+    )?;
+
+    temp.child(".claude/qa/with-offset.md").write_str(
+        "---\n\
+         title: With Offset\n\
+         link: with-offset\n\
+         type: qa\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174001\n\
+         created_at: 2025-10-23 14:00:00+02:00\n\
+         updated_at: 2025-10-24T09:30:00\n\
+         schema_version: 2\n\
+         ---\n\
+         Offset timestamps.\n",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["search", "hand", "written"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("./.claude/qa/date-only.md"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("manifest")
+        .assert()
+        .success();
+    temp.child(".claude/manifest.md")
+        .assert(predicate::str::contains("| 2025-10-23 |"))
+        .assert(predicate::str::contains("| 2025-10-24 |"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("validate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("no issues found"));
+
+    Ok(())
+}
+
+#[test]
+fn validate_rejects_inverted_and_future_timestamps() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude/qa").create_dir_all()?;
+
+    temp.child(".claude/qa/inverted.md").write_str(
+        "---\n\
+         title: Inverted\n\
+         link: inverted\n\
+         type: qa\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174002\n\
+         created_at: 2025-10-23T14:00:00Z\n\
+         updated_at: 2025-10-22T14:00:00Z\n\
+         ---\n",
+    )?;
+
+    temp.child(".claude/qa/future.md").write_str(
+        "---\n\
+         title: Future\n\
+         link: future\n\
+         type: qa\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174003\n\
+         created_at: 2999-01-01\n\
+         ---\n",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("validate")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "`updated_at` 2025-10-22T14:00:00Z precedes",
+        ))
+        .stdout(predicate::str::contains(
+            "`created_at` 2999-01-01T00:00:00Z is in the future",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn migrate_upgrades_legacy_front_matter() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude/patterns").create_dir_all()?;
+
+    let legacy = temp.child(".claude/patterns/legacy-note.md");
+    legacy.write_str(
+        "---\n\
+         title: Legacy Note\n\
+         link: legacy-note\n\
+         type: patterns\n\
+         ontological_relations:\n\
+         \x20 - retry-with-backoff\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174004\n\
+         created_at: 2025-10-23T14:00:00Z\n\
+         review:\n\
+         \x20 due: 2025-11-01\n\
+         ---\n\
+         Legacy body.\n",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["migrate", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Would migrate ./.claude/patterns/legacy-note.md (v1 → v2)",
+        ))
+        .stdout(predicate::str::contains("converted 1 string relation(s)"));
+    legacy.assert(predicate::str::contains("schema_version").not());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("migrate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Migrated 1 document(s)"));
+    legacy
+        .assert(predicate::str::contains("relates_to: retry-with-backoff"))
+        .assert(predicate::str::contains("updated_at: 2025-10-23T14:00:00Z"))
+        .assert(predicate::str::contains("schema_version: 2"))
+        .assert(predicate::str::contains("review:\n  due: 2025-11-01"))
+        .assert(predicate::str::contains("Legacy body."));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("migrate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "All documents already at schema version 2.",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn legacy_schema_is_reported_without_failing_strict() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude/qa/legacy-question.md").write_str(
+        "---\n\
+         title: Legacy Question\n\
+         link: legacy-question\n\
+         type: qa\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174005\n\
+         created_at: 2025-10-23T14:00:00Z\n\
+         ---\n\
+         Legacy body.\n",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["validate", "--strict"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "note: ./.claude/qa/legacy-question.md — Front matter schema version 1 is older than 2",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn fmt_rewrites_to_canonical_form() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude/qa").create_dir_all()?;
+
+    let drifted = temp.child(".claude/qa/drifted.md");
+    drifted.write_str(
+        "---\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174005\n\
+         owner: platform-team\n\
+         tags: [Rust, auth, rust]\n\
+         type: qa\n\
+         link: drifted\n\
+         title: Drifted\n\
+         created_at: 2025-10-23 14:00:00+02:00\n\
+         schema_version: 2\n\
+         ---\r\n\
+         Body line.\r\n\r\n\r\n",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["fmt", "--check"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Would reformat ./.claude/qa/drifted.md",
+        ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("fmt")
+        .assert()
+        .success();
+    drifted.assert(
+        "---\n\
+         title: Drifted\n\
+         link: drifted\n\
+         type: qa\n\
+         ontological_relations: []\n\
+         tags:\n\
+         - auth\n\
+         - rust\n\
+         created_at: 2025-10-23T12:00:00Z\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174005\n\
+         schema_version: 2\n\
+         owner: platform-team\n\
+         ---\n\
+         Body line.\n",
+    );
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["fmt", "--check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("All documents already formatted."));
+
+    Ok(())
+}
+
+#[test]
+fn bracketed_and_uuid_relations_are_normalized() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude/qa").create_dir_all()?;
+
+    temp.child(".claude/qa/drizzle-docs.md").write_str(
+        "---\n\
+         title: Drizzle Docs\n\
+         link: drizzle-docs\n\
+         type: qa\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174020\n\
+         created_at: 2025-10-23T14:00:00Z\n\
+         schema_version: 2\n\
+         ---\n",
+    )?;
+    let auth = temp.child(".claude/qa/auth-module.md");
+    auth.write_str(
+        "---\n\
+         title: Auth Module\n\
+         link: auth-module\n\
+         type: qa\n\
+         ontological_relations:\n\
+         \x20 - relates_to: [[drizzle-docs]]\n\
+         \x20 - relates_to: \"[[drizzle-docs|the docs]]\"\n\
+         \x20 - relates_to: 123E4567-E89B-12D3-A456-426614174020\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174021\n\
+         created_at: 2025-10-23T14:00:00Z\n\
+         schema_version: 2\n\
+         ---\n",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["backlinks", "drizzle-docs"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("./.claude/qa/auth-module.md"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("fmt")
+        .assert()
+        .success();
+    auth.assert(predicate::str::contains(
+        "- relates_to: drizzle-docs\n- relates_to: drizzle-docs\n- relates_to: 123e4567-e89b-12d3-a456-426614174020\n",
+    ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["link", "auth-module", "drizzle-docs"])
+        .assert()
+        .success();
+    temp.child(".claude/qa/drizzle-docs.md")
+        .assert(predicate::str::contains("relates_to: auth-module"));
+
+    Ok(())
+}
+
+#[test]
+fn only_fmt_canonicalizes_tags() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude").create_dir_all()?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "Tagged", "-t", "qa", "-g", "Rust", "-g", "async"])
+        .write_stdin("\n")
+        .assert()
+        .success();
+    let tagged = temp.child(".claude/qa/tagged.md");
+    tagged.assert(predicate::str::contains("tags:\n- Rust\n- async\n"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("fmt")
+        .assert()
+        .success();
+    tagged.assert(predicate::str::contains("tags:\n- async\n- rust\n"));
+
+    Ok(())
+}
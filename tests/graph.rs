@@ -0,0 +1,352 @@
+use assert_cmd::Command;
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+fn kb() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude").create_dir_all()?;
+
+    for (title, doc_type, tags) in [
+        ("Auth Module", "metadata", "auth"),
+        ("Token Refresh", "patterns", "auth"),
+        ("Retry Backoff", "patterns", "network"),
+        ("Release Plan", "plans", ""),
+    ] {
+        Command::cargo_bin("kb-claude")?
+            .current_dir(temp.path())
+            .args(["new", title, "-t", doc_type])
+            .write_stdin(format!("{tags}\n\n\n"))
+            .assert()
+            .success();
+    }
+
+    for (source, target) in [
+        ("auth-module", "token-refresh"),
+        ("token-refresh", "retry-backoff"),
+    ] {
+        Command::cargo_bin("kb-claude")?
+            .current_dir(temp.path())
+            .args(["link", source, target])
+            .assert()
+            .success();
+    }
+
+    Ok(temp)
+}
+
+#[test]
+fn graph_exports_formats_and_scopes() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("graph")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("digraph kb {"))
+        .stdout(predicate::str::contains(
+            "\"auth-module\" [label=\"Auth Module\", type=\"metadata\", tags=\"auth\"];",
+        ))
+        .stdout(predicate::str::contains(
+            "\"auth-module\" -> \"token-refresh\";",
+        ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["graph", "--format", "mermaid", "--tag", "auth"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("graph LR"))
+        .stdout(predicate::str::contains("[\"Token Refresh\"]"))
+        .stdout(predicate::str::contains("Retry Backoff").not());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args([
+            "graph",
+            "--format",
+            "json",
+            "--around",
+            "auth-module",
+            "--depth",
+            "1",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "\"path\": \"./.claude/metadata/auth-module.md\"",
+        ))
+        .stdout(predicate::str::contains("retry-backoff").not());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["graph", "--format", "graphml", "-o", "kb.graphml"])
+        .assert()
+        .success();
+    temp.child("kb.graphml").assert(predicate::str::contains(
+        "<edge source=\"token-refresh\" target=\"retry-backoff\"><data key=\"kind\">relation</data></edge>",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn related_and_path_traverse_relations() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["related", "auth-module", "--depth", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "1. ./.claude/patterns/token-refresh.md — Token Refresh (type: patterns, hops: 1)",
+        ))
+        .stdout(predicate::str::contains("retry-backoff").not());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["related", "auth-module"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Retry Backoff (type: patterns, hops: 2)",
+        ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["path", "auth-module", "retry-backoff"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "auth-module → token-refresh → retry-backoff",
+        ))
+        .stdout(predicate::str::contains("2 hop(s)"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["path", "auth-module", "release-plan"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No relation path connects"));
+
+    Ok(())
+}
+
+#[test]
+fn backlinks_are_listed_and_synced() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["backlinks", "retry-backoff"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "1. ./.claude/patterns/token-refresh.md — Token Refresh (type: patterns)",
+        ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["backlinks", "--sync"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Updated ./.claude/metadata/auth-module.md",
+        ));
+
+    let auth = temp.child(".claude/metadata/auth-module.md");
+    auth.assert(predicate::str::contains(
+        "<!-- kb-claude:backlinks:start -->\n## Referenced by\n\n- [Token Refresh](../patterns/token-refresh.md)\n<!-- kb-claude:backlinks:end -->\n",
+    ));
+    temp.child(".claude/plans/release-plan.md")
+        .assert(predicate::str::contains("kb-claude:backlinks").not());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["backlinks", "--sync"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Backlink sections already up to date.",
+        ));
+
+    temp.child(".claude/config.yml")
+        .write_str("backlinks_section: true\n")?;
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["link", "release-plan", "auth-module"])
+        .assert()
+        .success();
+    auth.assert(predicate::str::contains(
+        "- [Release Plan](../plans/release-plan.md)\n- [Token Refresh](../patterns/token-refresh.md)\n",
+    ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "Rollback [draft] (v2)", "-t", "plans"])
+        .write_stdin("\nauth-module\n\n")
+        .assert()
+        .success();
+    auth.assert(predicate::str::contains(
+        "- [Rollback \\[draft\\] \\(v2\\)](../plans/rollback-draft-v2.md)\n",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn orphans_and_graph_stats() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("orphans")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "1. ./.claude/plans/release-plan.md — Release Plan (type: plans)",
+        ))
+        .stdout(predicate::str::contains("auth-module").not());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["stats", "--graph"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Documents: 4"))
+        .stdout(predicate::str::contains("  patterns: 2"))
+        .stdout(predicate::str::contains("Orphans: 1"))
+        .stdout(predicate::str::contains(
+            "Connected components: 2 (largest: 3)",
+        ))
+        .stdout(predicate::str::contains(
+            "  1. token-refresh — Token Refresh (degree: 2)",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn body_wikilinks_count_as_relations() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+
+    temp.child(".claude/qa/wiki-notes.md").write_str(
+        "---\n\
+         title: Wiki Notes\n\
+         link: wiki-notes\n\
+         type: qa\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174010\n\
+         created_at: 2025-10-23T14:00:00Z\n\
+         schema_version: 2\n\
+         ---\n\
+         See [[release-plan|the plan]] and [[Retry Backoff#jitter]].\n\
+         Ignore `[[in-code]]` and [[missing-doc]].\n\
+         ```\n\
+         [[fenced-example]]\n\
+         ```\n",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["backlinks", "release-plan"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "./.claude/qa/wiki-notes.md — Wiki Notes",
+        ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["graph", "--around", "wiki-notes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "\"wiki-notes\" -> \"retry-backoff\" [style=dashed];",
+        ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["validate", "--strict"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Wikilink `[[missing-doc]]` on body line 2 does not resolve",
+        ))
+        .stdout(predicate::str::contains("in-code").not())
+        .stdout(predicate::str::contains("fenced-example").not());
+
+    Ok(())
+}
+
+#[test]
+fn suggest_links_proposes_similar_unrelated_documents() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+
+    for (doc_type, link, title, body) in [
+        (
+            "debug_history",
+            "token-cache-expiry",
+            "Token Cache Expiry",
+            "Stale refresh tokens stayed in the cache after expiry; clearing the token cache fixed login loops.",
+        ),
+        (
+            "qa",
+            "why-tokens-expire",
+            "Why Tokens Expire",
+            "Refresh tokens expire so a leaked token cache cannot grant access forever.",
+        ),
+    ] {
+        temp.child(format!(".claude/{doc_type}/{link}.md")).write_str(&format!(
+            "---\n\
+             title: {title}\n\
+             link: {link}\n\
+             type: {doc_type}\n\
+             uuid: {}\n\
+             created_at: 2025-10-23T14:00:00Z\n\
+             schema_version: 2\n\
+             ---\n\
+             {body}\n",
+            uuid::Uuid::new_v4()
+        ))?;
+    }
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["suggest-links", "token-cache-expiry"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "token-cache-expiry — Token Cache Expiry",
+        ))
+        .stdout(predicate::str::contains(
+            "  1. ./.claude/qa/why-tokens-expire.md — Why Tokens Expire (score: ",
+        ))
+        .stdout(predicate::str::contains("release-plan").not())
+        .stdout(predicate::str::contains("Run with --apply"));
+    temp.child(".claude/qa/why-tokens-expire.md")
+        .assert(predicate::str::contains("relates_to").not());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["suggest-links", "token-cache-expiry", "--top", "1", "--apply"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Linked ./.claude/debug_history/token-cache-expiry.md <-> ./.claude/qa/why-tokens-expire.md",
+        ));
+    temp.child(".claude/qa/why-tokens-expire.md")
+        .assert(predicate::str::contains("relates_to: token-cache-expiry"));
+    temp.child(".claude/debug_history/token-cache-expiry.md")
+        .assert(predicate::str::contains("relates_to: why-tokens-expire"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["suggest-links", "why-tokens-expire", "--top", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("token-cache-expiry").not());
+
+    Ok(())
+}
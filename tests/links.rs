@@ -0,0 +1,238 @@
+use assert_cmd::Command;
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+mod common;
+use common::write_titled_doc;
+
+#[test]
+fn validate_reports_broken_relative_links() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    write_titled_doc(
+        &temp,
+        "patterns",
+        "retry-with-backoff",
+        "Retry With Backoff",
+        "Retry.",
+    )?;
+    temp.child(".claude/patterns/diagram.png")
+        .write_str("png")?;
+    write_titled_doc(
+        &temp,
+        "debug_history",
+        "timeout-storm",
+        "Timeout Storm",
+        "See [retry](../patterns/retry-with-backoff.md) and ![diagram](../patterns/diagram.png).\n\
+         Also [gone](../patterns/missing.md \"title\"), [web](https://example.com) and `[code](nope.md)`.",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("validate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Link `../patterns/missing.md` on body line 2 points to a missing file",
+        ))
+        .stdout(predicate::str::contains("retry-with-backoff.md`").not())
+        .stdout(predicate::str::contains("nope.md").not());
+
+    Ok(())
+}
+
+#[test]
+fn mv_rewrites_links_and_references() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    write_titled_doc(
+        &temp,
+        "qa",
+        "retry-with-backoff",
+        "Retry With Backoff",
+        "Back to [storm](../debug_history/timeout-storm.md#timeline).",
+    )?;
+    write_titled_doc(
+        &temp,
+        "debug_history",
+        "timeout-storm",
+        "Timeout Storm",
+        "See [retry](../qa/retry-with-backoff.md) and [[retry-with-backoff|the pattern]].",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["mv", "retry-with-backoff", "patterns"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Moved ./.claude/qa/retry-with-backoff.md → ./.claude/patterns/retry-with-backoff.md",
+        ))
+        .stdout(predicate::str::contains(
+            "Updated references in ./.claude/debug_history/timeout-storm.md",
+        ));
+
+    temp.child(".claude/qa/retry-with-backoff.md")
+        .assert(predicate::path::missing());
+    temp.child(".claude/patterns/retry-with-backoff.md")
+        .assert(predicate::str::contains("type: patterns"))
+        .assert(predicate::str::contains(
+            "[storm](../debug_history/timeout-storm.md#timeline)",
+        ));
+    temp.child(".claude/debug_history/timeout-storm.md")
+        .assert(predicate::str::contains(
+            "[retry](../patterns/retry-with-backoff.md)",
+        ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args([
+            "mv",
+            "retry-with-backoff",
+            ".claude/patterns/retry-backoff.md",
+        ])
+        .assert()
+        .success();
+
+    temp.child(".claude/patterns/retry-backoff.md")
+        .assert(predicate::str::contains("link: retry-backoff"));
+    temp.child(".claude/debug_history/timeout-storm.md")
+        .assert(predicate::str::contains(
+            "[retry](../patterns/retry-backoff.md) and [[retry-backoff|the pattern]]",
+        ));
+
+    for destination in [
+        "../elsewhere.md",
+        ".claude/retry.md",
+        ".claude/notes/retry.md",
+    ] {
+        Command::cargo_bin("kb-claude")?
+            .current_dir(temp.path())
+            .args(["mv", "retry-backoff", destination])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "must be inside a document type directory",
+            ));
+    }
+    temp.child(".claude/patterns/retry-backoff.md")
+        .assert(predicate::path::exists());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["mv", "retry-backoff", ".claude/qa/Timeout Storm.md"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Link `timeout-storm` is already used by ./.claude/debug_history/timeout-storm.md",
+        ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["mv", "retry-backoff", ".claude/qa/sub/Retry Notes.md"])
+        .assert()
+        .success();
+    temp.child(".claude/qa/sub/retry-notes.md")
+        .assert(predicate::str::contains("link: retry-notes"))
+        .assert(predicate::str::contains("type: qa"));
+    temp.child(".claude/debug_history/timeout-storm.md")
+        .assert(predicate::str::contains(
+            "[retry](../qa/sub/retry-notes.md) and [[retry-notes|the pattern]]",
+        ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("validate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("points to a missing file").not())
+        .stdout(predicate::str::contains("does not match directory").not());
+
+    Ok(())
+}
+
+#[test]
+fn validate_checks_heading_anchors() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    write_titled_doc(
+        &temp,
+        "patterns",
+        "retry-with-backoff",
+        "Retry With Backoff",
+        "## Jitter & Limits\n\nDetails.",
+    )?;
+    write_titled_doc(
+        &temp,
+        "debug_history",
+        "timeout-storm",
+        "Timeout Storm",
+        "## Timeline\n\
+         See [[retry-with-backoff#Jitter & Limits]] and [limits](../patterns/retry-with-backoff.md#jitter--limits).\n\
+         Broken: [[retry-with-backoff#cooldown]], [up](#summary) and [[#timeline]].",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("validate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Wikilink `[[retry-with-backoff#cooldown]]` on body line 3 points to a missing heading",
+        ))
+        .stdout(predicate::str::contains(
+            "Link `#summary` on body line 3 points to a missing heading",
+        ))
+        .stdout(predicate::str::contains("Jitter").not())
+        .stdout(predicate::str::contains("jitter--limits").not())
+        .stdout(predicate::str::contains("#timeline").not());
+
+    Ok(())
+}
+
+#[test]
+fn toc_is_generated_and_refreshed() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    write_titled_doc(
+        &temp,
+        "patterns",
+        "retry-with-backoff",
+        "Retry With Backoff",
+        "Intro.\n\n## Usage\n\n### Jitter\n\n```\n## not a heading\n```",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["toc", "retry-with-backoff"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Updated table of contents"));
+
+    let doc = temp.child(".claude/patterns/retry-with-backoff.md");
+    doc.assert(predicate::str::contains(
+        "---\n<!-- kb-claude:toc:start -->\n## Contents\n\n- [Usage](#usage)\n  - [Jitter](#jitter)\n<!-- kb-claude:toc:end -->\n\nIntro.",
+    ));
+    doc.assert(predicate::str::contains("(#not-a-heading)").not());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["fmt", "--check", "--toc"])
+        .assert()
+        .success();
+
+    write_titled_doc(
+        &temp,
+        "qa",
+        "why-retry",
+        "Why Retry",
+        "## Answer\n\nBecause.",
+    )?;
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["fmt", "--check", "--toc"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Would reformat ./.claude/qa/why-retry.md",
+        ));
+
+    Ok(())
+}
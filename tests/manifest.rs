@@ -0,0 +1,477 @@
+use assert_cmd::Command;
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+mod common;
+use common::{kb, write_doc};
+
+#[test]
+fn manifest_renders_machine_readable_formats() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("manifest.json"));
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(
+        temp.child(".claude/manifest.json").path(),
+    )?)?;
+    let retry = json
+        .as_array()
+        .and_then(|entries| {
+            entries
+                .iter()
+                .find(|entry| entry["link"] == "retry-with-backoff")
+        })
+        .ok_or("retry entry missing")?;
+    assert_eq!(retry["uuid"], "123e4567-e89b-12d3-a456-426614174001");
+    assert_eq!(retry["type"], "patterns");
+    assert_eq!(retry["path"], "./.claude/patterns/retry-with-backoff.md");
+    assert_eq!(retry["relations"], serde_json::json!(["timeout-storm"]));
+    assert_eq!(retry["created_at"], "2025-10-20T09:00:00Z");
+    assert_eq!(retry["updated_at"], "2025-10-22T09:30:00Z");
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--format", "yaml"])
+        .assert()
+        .success();
+    temp.child(".claude/manifest.yaml")
+        .assert(predicate::str::contains("link: timeout-storm"))
+        .assert(predicate::str::contains("updated_at: 2025-10-21T12:00:00Z"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--format", "csv", "-o", "kb.csv"])
+        .assert()
+        .success();
+    temp.child("kb.csv")
+        .assert(predicate::str::starts_with(
+            "title,link,type,uuid,path,tags,relations,summary,created_at,updated_at\n",
+        ))
+        .assert(predicate::str::contains(
+            "Retry with backoff,retry-with-backoff,patterns,123e4567-e89b-12d3-a456-426614174001,./.claude/patterns/retry-with-backoff.md,network;resilience,timeout-storm,Retry failed calls with exponential backoff and jitter.,2025-10-20T09:00:00Z,2025-10-22T09:30:00Z",
+        ))
+        .assert(predicate::str::contains("\"Timeout storm, part 1\",timeout-storm"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--format", "html"])
+        .assert()
+        .success();
+    temp.child(".claude/manifest.html")
+        .assert(predicate::str::starts_with("<!DOCTYPE html>"))
+        .assert(predicate::str::contains("<td>Retry with backoff</td>"))
+        .assert(predicate::str::contains(
+            "<a href=\"patterns/retry-with-backoff.md\">./.claude/patterns/retry-with-backoff.md</a>",
+        ))
+        .assert(predicate::str::contains(
+            "<a href=\"debug_history/timeout-storm.md\">timeout-storm</a>",
+        ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--format", "html", "-o", "docs/kb.html"])
+        .assert()
+        .success();
+    temp.child("docs/kb.html").assert(predicate::str::contains(
+        "<a href=\"../.claude/patterns/retry-with-backoff.md\">",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn manifest_escapes_cells_and_links_documents() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+    write_doc(
+        &temp,
+        "qa",
+        "pipes-and-ticks",
+        "title: \"Use `a|b` pipes\\nsafely\"\n\
+         ontological_relations:\n  - relates_to: 123e4567-e89b-12d3-a456-426614174001\n  - relates_to: gone\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174003\n\
+         created_at: 2025-10-22T08:00:00Z",
+        "Body.",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("manifest")
+        .assert()
+        .success();
+
+    temp.child(".claude/manifest.md")
+        .assert(predicate::str::contains(
+            "| Use \\`a\\|b\\` pipes safely | qa | [./.claude/qa/pipes-and-ticks.md](qa/pipes-and-ticks.md) | — | [123e4567-e89b-12d3-a456-426614174001](patterns/retry-with-backoff.md), gone | 2025-10-22 |",
+        ))
+        .assert(predicate::str::contains(
+            "| Retry with backoff | patterns | [./.claude/patterns/retry-with-backoff.md](patterns/retry-with-backoff.md) | network, resilience | [timeout-storm](debug_history/timeout-storm.md) | 2025-10-22 |",
+        ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "-o", "docs/kb.md"])
+        .assert()
+        .success();
+    temp.child("docs/kb.md").assert(predicate::str::contains(
+        "[./.claude/debug_history/timeout-storm.md](../.claude/debug_history/timeout-storm.md)",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn manifest_check_reports_drift() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--check"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("out of date"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("manifest")
+        .assert()
+        .success();
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Manifest ./.claude/manifest.md is up to date.",
+        ));
+
+    write_doc(
+        &temp,
+        "qa",
+        "new-question",
+        "title: New question\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174004\n\
+         created_at: 2025-10-23T08:00:00Z",
+        "Body.",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--check"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("--- a/.claude/manifest.md"))
+        .stdout(predicate::str::contains("+++ b/.claude/manifest.md"))
+        .stdout(predicate::str::contains("\n+| New question | qa |"));
+    temp.child(".claude/manifest.md")
+        .assert(predicate::str::contains("New question").not());
+
+    Ok(())
+}
+
+#[test]
+fn manifest_groups_sections_and_writes_type_indexes() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--group-by", "type", "--header", "--type-index"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Wrote manifest to ./.claude/patterns/index.md",
+        ));
+
+    let manifest = std::fs::read_to_string(temp.child(".claude/manifest.md").path())?;
+    assert!(manifest.starts_with(
+        "# Knowledge Base Manifest\n\n- Documents: 2\n- By type: debug_history 1, patterns 1\n- Tags: 2\n- Last updated: 2025-10-22\n\n## debug_history (1)\n\n| Title |"
+    ));
+    assert!(manifest.contains("\n## patterns (1)\n\n| Title |"));
+
+    temp.child(".claude/patterns/index.md")
+        .assert(predicate::str::starts_with(
+            "<!-- kb-claude:index -->\n# patterns (1)\n",
+        ))
+        .assert(predicate::str::contains(
+            "[./.claude/patterns/retry-with-backoff.md](retry-with-backoff.md)",
+        ))
+        .assert(predicate::str::contains(
+            "[timeout-storm](../debug_history/timeout-storm.md)",
+        ))
+        .assert(predicate::str::contains("| Timeout storm").not());
+    temp.child(".claude/qa/index.md")
+        .assert(predicate::path::missing());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("validate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("index.md").not());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--group-by", "tag", "-o", "by-tag.md"])
+        .assert()
+        .success();
+    let by_tag = std::fs::read_to_string(temp.child("by-tag.md").path())?;
+    let network = by_tag
+        .find("## network (1)")
+        .ok_or("network section missing")?;
+    let resilience = by_tag
+        .find("## resilience (1)")
+        .ok_or("resilience section missing")?;
+    let untagged = by_tag
+        .find("## (untagged) (1)")
+        .ok_or("untagged section missing")?;
+    assert!(network < resilience && resilience < untagged);
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--group-by", "month", "-o", "by-month.md"])
+        .assert()
+        .success();
+    temp.child("by-month.md")
+        .assert(predicate::str::starts_with("## 2025-10 (2)\n"));
+
+    Ok(())
+}
+
+#[test]
+fn manifest_type_index_keeps_documents_named_index() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+    write_doc(
+        &temp,
+        "qa",
+        "index",
+        "title: Index\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174003\n\
+         created_at: 2025-10-23T08:00:00Z",
+        "Where to find things.",
+    )?;
+
+    for _ in 0..2 {
+        Command::cargo_bin("kb-claude")?
+            .current_dir(temp.path())
+            .args(["manifest", "--type-index"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Warning: skipping the qa index; ./.claude/qa/index.md is a document",
+            ))
+            .stdout(predicate::str::contains(
+                "Wrote manifest to ./.claude/patterns/index.md",
+            ));
+    }
+
+    temp.child(".claude/qa/index.md")
+        .assert(predicate::str::contains("Where to find things."))
+        .assert(predicate::str::contains("kb-claude:index").not());
+    temp.child(".claude/manifest.md")
+        .assert(predicate::str::contains("| Index | qa |"));
+
+    Ok(())
+}
+
+#[test]
+fn manifest_shows_summaries() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+    write_doc(
+        &temp,
+        "qa",
+        "explicit-summary",
+        "title: Explicit summary\n\
+         summary: \"  Why retries need jitter.  \"\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174005\n\
+         created_at: 2025-10-22T08:00:00Z",
+        "Ignored body text.",
+    )?;
+    write_doc(
+        &temp,
+        "qa",
+        "long-body",
+        "title: Long body\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174006\n\
+         created_at: 2025-10-22T08:00:00Z",
+        "<!-- kb-claude:toc:start -->\n## Contents\n\n- [Context](#context)\n<!-- kb-claude:toc:end -->\n\n\
+         # Context\n\n\
+         The gateway retried every request immediately, which amplified the outage.\n\
+         Later we added exponential backoff with full jitter and capped the attempts at five, which kept the upstream pool from saturating again.\n\n\
+         Second paragraph.",
+    )?;
+
+    write_doc(
+        &temp,
+        "qa",
+        "bold-start",
+        "title: Bold start\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174007\n\
+         created_at: 2025-10-22T08:00:00Z",
+        "- **Bold** text opens the list.",
+    )?;
+    write_doc(
+        &temp,
+        "qa",
+        "fenced-body",
+        "title: Fenced body\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174008\n\
+         created_at: 2025-10-22T08:00:00Z",
+        "Run the probe\n```\ncurl localhost\n```\nthen read the logs.",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("manifest")
+        .assert()
+        .success();
+
+    temp.child(".claude/manifest.md")
+        .assert(predicate::str::contains(
+            "| Title | Type | Path | Tags | Relations | Updated | Summary |",
+        ))
+        .assert(predicate::str::contains(
+            "| 2025-10-22 | **Bold** text opens the list. |",
+        ))
+        .assert(predicate::str::contains("| 2025-10-22 | Run the probe |"))
+        .assert(predicate::str::contains(
+            "| 2025-10-22 | Why retries need jitter. |",
+        ))
+        .assert(predicate::str::contains(
+            "| 2025-10-22 | The gateway retried every request immediately, which amplified the outage. |",
+        ))
+        .assert(predicate::str::contains("Ignored body text").not())
+        .assert(predicate::str::contains("| Contents").not());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["search", "jitter"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("explicit-summary.md"));
+
+    Ok(())
+}
+
+#[test]
+fn manifest_renders_user_templates() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+    temp.child(".claude/templates/manifest.md").write_str(
+        "# Team KB ({{ count }} entries, updated {{ updated }})\n\
+         \n\
+         See the [dashboard](https://example.com/kb).\n\
+         \n\
+         | Entry | Owner link | Summary |\n\
+         |-------|------------|---------|\n\
+         {{#each entries}}\n\
+         | [{{ title | cell }}]({{ href }}) | {{ link }} | {{#if summary}}{{ summary | cell }}{{else}}n/a{{/if}} |\n\
+         {{/each}}\n\
+         \n\
+         {{#each types}}\n\
+         - {{ name }}: {{ count }}\n\
+         {{/each}}\n\
+         \n\
+         {{ table }}\n",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("manifest")
+        .assert()
+        .success();
+
+    let manifest = std::fs::read_to_string(temp.child(".claude/manifest.md").path())?;
+    assert!(manifest.starts_with(
+        "# Team KB (2 entries, updated 2025-10-22)\n\nSee the [dashboard](https://example.com/kb).\n\n| Entry | Owner link | Summary |\n|-------|------------|---------|\n| [Retry with backoff](patterns/retry-with-backoff.md) | retry-with-backoff | Retry failed calls with exponential backoff and jitter. |\n| [Timeout storm, part 1](debug_history/timeout-storm.md) | timeout-storm |"
+    ));
+    assert!(manifest.contains("\n\n- debug_history: 1\n- patterns: 1\n\n| Title | Type | Path |"));
+
+    temp.child("broken.md")
+        .write_str("{{#each entries}}{{ title }}")?;
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--template", "broken.md"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Missing `{{/each}}` in template"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--format", "json", "--template", "broken.md"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--template only applies to the Markdown manifest",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn manifest_is_deterministic_across_timezones() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    for (doc_type, link, uuid) in [
+        ("qa", "same-title-b", "123e4567-e89b-12d3-a456-426614174007"),
+        (
+            "patterns",
+            "same-title-a",
+            "123e4567-e89b-12d3-a456-426614174008",
+        ),
+    ] {
+        write_doc(
+            &temp,
+            doc_type,
+            link,
+            &format!(
+                "title: Same title\n\
+                 uuid: {uuid}\n\
+                 created_at: 2025-10-22T23:30:00Z"
+            ),
+            "Body.",
+        )?;
+    }
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("manifest")
+        .assert()
+        .success();
+    let manifest = std::fs::read_to_string(temp.child(".claude/manifest.md").path())?;
+    let first = manifest.find("same-title-a.md").ok_or("missing a")?;
+    let second = manifest.find("same-title-b.md").ok_or("missing b")?;
+    assert!(first < second);
+    assert!(manifest.contains("| 2025-10-22 |"));
+
+    temp.child(".claude/config.yml")
+        .write_str("display_timezone: Europe/Berlin\n")?;
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("manifest")
+        .assert()
+        .success();
+    temp.child(".claude/manifest.md")
+        .assert(predicate::str::contains("| 2025-10-23 |"))
+        .assert(predicate::str::contains("2025-10-22").not());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["manifest", "--check", "--timezone", "UTC"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("+| Same title"));
+
+    temp.child(".claude/config.yml")
+        .write_str("display_timezone: Mars/Olympus\n")?;
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("manifest")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("config.yml"));
+
+    Ok(())
+}
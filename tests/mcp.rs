@@ -0,0 +1,167 @@
+use assert_cmd::Command;
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use serde_json::{json, Value};
+
+mod common;
+use common::kb;
+
+fn session(temp: &TempDir, requests: &[Value]) -> Result<Vec<Value>, Box<dyn std::error::Error>> {
+    let input: String = requests
+        .iter()
+        .map(|request| format!("{request}\n"))
+        .collect();
+    let output = Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("mcp")
+        .write_stdin(input)
+        .output()?;
+    assert!(output.status.success());
+
+    let mut responses = String::from_utf8(output.stdout)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<Vec<Value>, _>>()?;
+    responses.sort_by_key(|response| response["id"].as_i64());
+    Ok(responses)
+}
+
+fn call(id: i64, name: &str, arguments: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments },
+    })
+}
+
+fn text(response: &Value) -> &str {
+    response["result"]["content"][0]["text"]
+        .as_str()
+        .unwrap_or_default()
+}
+
+#[test]
+fn mcp_exposes_tools_and_resources() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+
+    let responses = session(
+        &temp,
+        &[
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": { "protocolVersion": "2025-06-18", "capabilities": {} },
+            }),
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+            call(3, "search", json!({ "query": "exponential" })),
+            call(
+                4,
+                "new",
+                json!({ "title": "Retry with backoff", "type": "patterns" }),
+            ),
+            call(
+                5,
+                "new",
+                json!({
+                    "title": "Circuit breaker",
+                    "type": "patterns",
+                    "body": "Stop calling a failing service.",
+                    "tags": [" resilience ", "", "resilience"],
+                    "relates_to": ["[[retry-with-backoff]]"],
+                }),
+            ),
+            call(
+                6,
+                "link",
+                json!({ "source": "circuit-breaker", "target": "retry-with-backoff" }),
+            ),
+            call(7, "get", json!({ "link": "retry-with-backoff" })),
+            call(8, "validate", json!({})),
+            json!({ "jsonrpc": "2.0", "id": 9, "method": "resources/list" }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 10,
+                "method": "resources/read",
+                "params": { "uri": "kb://circuit-breaker" },
+            }),
+            json!({ "jsonrpc": "2.0", "id": 11, "method": "unknown/method" }),
+            call(
+                12,
+                "new",
+                json!({ "title": "Retry with backoff", "type": "qa", "allow_duplicate": true }),
+            ),
+        ],
+    )?;
+    assert_eq!(responses.len(), 12, "notifications must not be answered");
+
+    assert_eq!(responses[0]["result"]["protocolVersion"], "2025-06-18");
+    assert_eq!(responses[0]["result"]["serverInfo"]["name"], "kb-claude");
+
+    let tools: Vec<&str> = responses[1]["result"]["tools"]
+        .as_array()
+        .ok_or("tools missing")?
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    assert_eq!(tools, ["search", "get", "new", "link", "validate"]);
+
+    let hits: Value = serde_json::from_str(text(&responses[2]))?;
+    assert_eq!(hits[0]["link"], "retry-with-backoff");
+
+    assert_eq!(responses[3]["result"]["isError"], true);
+    assert!(text(&responses[3]).contains("Possible duplicates"));
+
+    assert_eq!(responses[4]["result"]["isError"], false);
+    temp.child(".claude/patterns/circuit-breaker.md")
+        .assert(predicates::str::contains("relates_to: retry-with-backoff"))
+        .assert(predicates::str::contains("tags:\n- resilience\ncreated_at"));
+    temp.child(".claude/patterns/retry-with-backoff.md")
+        .assert(predicates::str::contains("relates_to: circuit-breaker"));
+
+    assert!(text(&responses[5]).contains("Relations already existed"));
+
+    let doc: Value = serde_json::from_str(text(&responses[6]))?;
+    assert_eq!(doc["title"], "Retry with backoff");
+    assert_eq!(
+        doc["relations"],
+        json!(["timeout-storm", "circuit-breaker"])
+    );
+    assert_eq!(doc["incoming"], json!(["circuit-breaker"]));
+
+    assert_eq!(responses[7]["result"]["isError"], false);
+
+    let uris: Vec<&str> = responses[8]["result"]["resources"]
+        .as_array()
+        .ok_or("resources missing")?
+        .iter()
+        .filter_map(|resource| resource["uri"].as_str())
+        .collect();
+    assert_eq!(
+        uris,
+        [
+            "kb://timeout-storm",
+            "kb://circuit-breaker",
+            "kb://retry-with-backoff"
+        ]
+    );
+
+    let contents = &responses[9]["result"]["contents"][0];
+    assert_eq!(contents["mimeType"], "text/markdown");
+    assert!(contents["text"]
+        .as_str()
+        .is_some_and(|text| text.contains("Stop calling a failing service.")));
+
+    assert_eq!(responses[10]["error"]["code"], -32601);
+
+    assert_eq!(responses[11]["result"]["isError"], false);
+    let created: Value = serde_json::from_str(text(&responses[11]))?;
+    assert_eq!(created["path"], "./.claude/qa/retry-with-backoff.md");
+    assert!(created["warning"]
+        .as_str()
+        .is_some_and(|warning| warning.contains("`retry-with-backoff` is already used")));
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+
+mod common;
+use common::{kb, write_titled_doc};
+
+struct Server {
+    child: Child,
+    address: String,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn serve(temp: &TempDir) -> Result<Server, Box<dyn std::error::Error>> {
+    let mut child = Command::new(assert_cmd::cargo::cargo_bin("kb-claude"))
+        .current_dir(temp.path())
+        .args(["serve", "--port", "0"])
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let mut stdout = BufReader::new(child.stdout.take().ok_or("no stdout")?);
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    // Keep reading so later "Reloaded" lines never hit a closed pipe.
+    thread::spawn(move || stdout.lines().for_each(drop));
+    let address = line
+        .split("http://")
+        .nth(1)
+        .and_then(|rest| rest.split('/').next())
+        .ok_or_else(|| format!("unexpected banner: {line}"))?
+        .to_string();
+    Ok(Server { child, address })
+}
+
+fn get(server: &Server, path: &str) -> Result<(u16, String), Box<dyn std::error::Error>> {
+    let mut stream = TcpStream::connect(&server.address)?;
+    write!(
+        stream,
+        "GET {path} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        server.address
+    )?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or("malformed response")?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .ok_or("missing status")?
+        .parse()?;
+    Ok((status, body.to_string()))
+}
+
+#[test]
+fn serve_answers_pages_api_and_reloads() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = kb()?;
+    let server = serve(&temp)?;
+
+    let (status, body) = get(&server, "/")?;
+    assert_eq!(status, 200);
+    assert!(body.contains("Knowledge Base"));
+
+    let (status, body) = get(&server, "/patterns/retry-with-backoff.html")?;
+    assert_eq!(status, 200);
+    assert!(body.contains("<h1>Retry with backoff</h1>"));
+
+    let (_, body) = get(&server, "/api/search?q=exponential+backoff")?;
+    let hits: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(hits[0]["link"], "retry-with-backoff");
+    assert_eq!(hits.as_array().map(Vec::len), Some(1));
+
+    let (status, body) = get(&server, "/api/doc/[[timeout-storm]]")?;
+    assert_eq!(status, 200);
+    let doc: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(doc["title"], "Timeout storm, part 1");
+    assert_eq!(doc["incoming"], serde_json::json!(["retry-with-backoff"]));
+
+    let (status, _) = get(&server, "/api/doc/missing")?;
+    assert_eq!(status, 404);
+
+    let (_, body) = get(&server, "/api/graph")?;
+    let graph: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(graph["nodes"].as_array().map(Vec::len), Some(2));
+
+    let (status, body) = get(&server, "/manifest.md")?;
+    assert_eq!(status, 200);
+    assert!(body.contains("| Retry with backoff |"));
+
+    let (status, body) = get(&server, "/patterns/retry-with-backoff.md")?;
+    assert_eq!(status, 200);
+    assert!(body.starts_with("---\n"));
+
+    #[cfg(unix)]
+    {
+        temp.child("secret.txt")
+            .write_str("outside the knowledge base")?;
+        std::os::unix::fs::symlink(
+            temp.child("secret.txt").path(),
+            temp.child(".claude/patterns/secret.txt").path(),
+        )?;
+        let (status, body) = get(&server, "/patterns/secret.txt")?;
+        assert_eq!(status, 404);
+        assert!(!body.contains("outside the knowledge base"));
+    }
+
+    write_titled_doc(
+        &temp,
+        "qa",
+        "why-jitter",
+        "Why add jitter",
+        "Jitter spreads retries so backoff does not synchronize clients.",
+    )?;
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        let (_, body) = get(&server, "/api/search?q=backoff")?;
+        let hits: serde_json::Value = serde_json::from_str(&body)?;
+        if hits.as_array().map(Vec::len) == Some(2) {
+            break;
+        }
+        assert!(Instant::now() < deadline, "server did not reload: {body}");
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    Ok(())
+}
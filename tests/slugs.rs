@@ -0,0 +1,289 @@
+use assert_cmd::Command;
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+mod common;
+use common::write_titled_doc;
+
+#[test]
+fn titles_are_transliterated_by_default() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude").create_dir_all()?;
+
+    for title in ["Café crash", "Straße reroute", "東京 outage"] {
+        Command::cargo_bin("kb-claude")?
+            .current_dir(temp.path())
+            .args(["new", title, "-t", "debug_history"])
+            .write_stdin("\n\n\n")
+            .assert()
+            .success();
+    }
+
+    temp.child(".claude/debug_history/cafe-crash.md")
+        .assert(predicate::str::contains("link: cafe-crash"));
+    temp.child(".claude/debug_history/strasse-reroute.md")
+        .assert(predicate::path::exists());
+    temp.child(".claude/debug_history/dong-jing-outage.md")
+        .assert(predicate::path::exists());
+
+    write_titled_doc(
+        &temp,
+        "debug_history",
+        "caf-rollback",
+        "Café rollback",
+        "Linked before slug policies existed.",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["validate", "--strict"])
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn unicode_slug_policy_keeps_letters() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude/config.yml")
+        .write_str("slug_policy: unicode\n")?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "Привет Мир", "-t", "qa"])
+        .write_stdin("\n\n\n")
+        .assert()
+        .success();
+
+    temp.child(".claude/qa/привет-мир.md")
+        .assert(predicate::str::contains("link: привет-мир"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "Follow up", "-t", "qa"])
+        .write_stdin("\n\nSee [[Привет Мир]].\n\n")
+        .assert()
+        .success();
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["validate", "--strict"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("does not resolve").not());
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["backlinks", "привет-мир"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("follow-up.md"));
+
+    Ok(())
+}
+
+#[test]
+fn colliding_titles_are_disambiguated_on_request() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude").create_dir_all()?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "Auth: fix", "-t", "patterns"])
+        .write_stdin("\n\n\n")
+        .assert()
+        .success();
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "auth fix", "-t", "patterns"])
+        .write_stdin("\n\n\n")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Possible duplicates of \"auth fix\":\n  - ./.claude/patterns/auth-fix.md — Auth: fix (similarity: 1.00)",
+        ))
+        .stdout(predicate::str::contains("Warning: creating it anyway"))
+        .stderr(predicate::str::contains("pass --disambiguate"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "auth fix", "-t", "patterns", "--allow-duplicate"])
+        .write_stdin("\n\n\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --disambiguate"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "auth fix", "-t", "patterns", "--disambiguate"])
+        .write_stdin("\n\n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("using `auth-fix-2` instead"));
+    temp.child(".claude/patterns/auth-fix-2.md")
+        .assert(predicate::str::contains("link: auth-fix-2"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "Auth fix", "-t", "patterns", "--disambiguate=uuid"])
+        .write_stdin("\n\n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(
+            "using `auth-fix-[0-9a-f]{8}` instead",
+        )?);
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["validate", "--strict"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "Auth fix", "-t", "qa", "--allow-duplicate"])
+        .write_stdin("\n\n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Warning: link `auth-fix` is already used by another document",
+        ));
+    temp.child(".claude/qa/auth-fix.md")
+        .assert(predicate::str::contains("link: auth-fix"));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["validate", "--strict"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "`link` `auth-fix` is also used by ./.claude/",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn dupes_lists_similar_titles() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude").create_dir_all()?;
+
+    for (title, doc_type) in [
+        ("Retry with backoff", "patterns"),
+        ("Token refresh race", "debug_history"),
+    ] {
+        Command::cargo_bin("kb-claude")?
+            .current_dir(temp.path())
+            .args(["new", title, "-t", doc_type])
+            .write_stdin("\n\n\n")
+            .assert()
+            .success();
+    }
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("dupes")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No likely duplicates found."));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "Retry backoffs", "-t", "qa", "--allow-duplicate"])
+        .write_stdin("\n\n\n")
+        .assert()
+        .success();
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .arg("dupes")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "1. ./.claude/patterns/retry-with-backoff.md ↔ ./.claude/qa/retry-backoffs.md (similarity: 1.00)",
+        ))
+        .stdout(predicate::str::contains("token-refresh-race").not());
+
+    Ok(())
+}
+
+#[test]
+fn piped_new_warns_about_duplicates_and_continues() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude").create_dir_all()?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "Retry with backoff", "-t", "patterns"])
+        .write_stdin("\n\n\n")
+        .assert()
+        .success();
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "Retry with back-off", "-t", "patterns"])
+        .write_stdin("\n\n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Possible duplicates of \"Retry with back-off\"",
+        ))
+        .stdout(predicate::str::contains("Warning: creating it anyway"));
+    temp.child(".claude/patterns/retry-with-back-off.md")
+        .assert(predicate::path::exists());
+
+    for title in ["API v1 migration", "API v2 migration"] {
+        Command::cargo_bin("kb-claude")?
+            .current_dir(temp.path())
+            .args(["new", title, "-t", "patterns"])
+            .write_stdin("\n\n\n")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Possible duplicates").not());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn link_suffix_needs_a_colliding_base_link() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    temp.child(".claude/patterns/foo-3.md").write_str(
+        "---\n\
+         title: Foo\n\
+         link: foo-3\n\
+         type: patterns\n\
+         uuid: 123e4567-e89b-12d3-a456-426614174010\n\
+         created_at: 2025-10-20T09:00:00Z\n\
+         schema_version: 2\n\
+         ---\n\
+         Body.\n",
+    )?;
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["validate", "--strict"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "`link` `foo-3` should match slugified title `foo`",
+        ));
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["new", "Foo", "-t", "qa"])
+        .write_stdin("\n\n\n")
+        .assert()
+        .success();
+
+    Command::cargo_bin("kb-claude")?
+        .current_dir(temp.path())
+        .args(["validate", "--strict"])
+        .assert()
+        .success();
+
+    Ok(())
+}